@@ -0,0 +1,13 @@
+@Deprecated
+public class M {
+    @Inject Object o;
+
+    @Override
+    public @Nullable String a(@Named("x") int x) { }
+
+    @Override
+    @Deprecated
+    void b() { }
+
+    public static final int X = 1;
+}
@@ -0,0 +1,5 @@
+@interface Marker {
+    String value() default "";
+
+    int[] numbers() default { 1, 2 };
+}
@@ -0,0 +1,19 @@
+enum Planet {
+    MERCURY(1.0),
+    VENUS(2.0) {
+        @Override
+        double mass() {
+            return 0;
+        }
+    };
+
+    private final double mass;
+
+    Planet(double mass) {
+        this.mass = mass;
+    }
+
+    double mass() {
+        return mass;
+    }
+}
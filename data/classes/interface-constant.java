@@ -0,0 +1,3 @@
+interface Shape {
+    int SIDES = 0;
+}
@@ -0,0 +1,5 @@
+sealed interface Shape permits Circle, Square { }
+
+final class Circle implements Shape { }
+
+non-sealed class Square extends Base implements Shape, Serializable { }
@@ -0,0 +1,7 @@
+package com.example;
+
+import java.util.Map;
+
+// Not documenting the class
+
+class Imports { }
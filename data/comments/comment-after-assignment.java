@@ -0,0 +1,2 @@
+int value = // the answer
+    42;
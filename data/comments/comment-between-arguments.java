@@ -0,0 +1,4 @@
+call(
+    first, // the first argument
+    second
+);
@@ -0,0 +1,5 @@
+class Empty {
+    void method() {
+        // nothing to do
+    }
+}
@@ -0,0 +1 @@
+call(/* first */ a, b /* last */);
@@ -0,0 +1,4 @@
+/*****************************************
+ * Banner    comments are   kept as written
+ *****************************************/
+class Banner { }
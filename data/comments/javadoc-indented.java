@@ -0,0 +1,10 @@
+class Calculator {
+    /**
+     * Adds the numbers together, returning their sum. Overflow wraps around, as with the {@code +}
+     * operator.
+     *
+     * @throws ArithmeticException never, but documented for the sake of the example, and long
+     *                             enough to wrap
+     */
+    int add(int left, int right) { }
+}
@@ -0,0 +1,6 @@
+class Calculator {
+    int add(int left, int right) {
+        /** Not documentation,   so kept   as written */
+        return left + right;
+    }
+}
@@ -0,0 +1,27 @@
+/**
+ * Adds and resets numbers, keeping a running total. Overflow wraps around, as with the {@code +}
+ * operator, rather than throwing.
+ *
+ * <p>For example:
+ * <pre>{@code
+ *     add(1, 2); // 3
+ * }</pre>
+ *
+ * @param <T>   the type of the numbers
+ * @param limit the largest total before wrapping
+ * @throws ArithmeticException never, but documented for the sake of the example, and long enough to
+ *                             wrap onto another line
+ */
+class Calculator {
+    /**
+     * Adds the numbers together.
+     *
+     * @param left  the first number
+     * @param right the second number
+     * @return the sum
+     */
+    int add(int left, int right) { }
+
+    /** Resets the calculator. */
+    void reset() { }
+}
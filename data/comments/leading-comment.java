@@ -0,0 +1,6 @@
+class Point {
+    int x;
+
+    // Moves the point
+    void move() { }
+}
@@ -0,0 +1,25 @@
+class A {
+    void a() {
+        if (a) {
+            b();
+        } // done
+        else {
+            c();
+        }
+
+        try {
+            b();
+        } // attempt
+        catch (Exception e) {
+            c();
+        } // handled
+        finally {
+            d();
+        }
+
+        do {
+            b();
+        } // loop
+        while (a);
+    }
+}
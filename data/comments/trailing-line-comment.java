@@ -0,0 +1,8 @@
+class Point {
+    int x; // horizontal
+    int y; // vertical
+
+    void move() {
+        x++; // right
+    }
+}
@@ -0,0 +1,6 @@
+void run() {
+    go();
+    if (ready) {
+        stop();
+    }
+}
@@ -0,0 +1,5 @@
+class Modern<T extends Comparable<? super T>> {
+    <N extends Number & Comparable<N>> N max(List<? extends N> values) { }
+
+    Map<String, List<?>> map = new HashMap<>();
+}
@@ -0,0 +1,12 @@
+package com.example.app;
+
+import java.util.List;
+import java.util.Map;
+
+import javax.inject.Inject;
+
+import com.example.Thing;
+
+import static java.util.Objects.requireNonNull;
+
+class Grouped { }
@@ -0,0 +1,3 @@
+﻿class BomCrlf {
+    int x;
+}
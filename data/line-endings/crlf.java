@@ -0,0 +1,8 @@
+class Crlf {
+    void run() {
+        String text = """
+            a
+            b
+            """;
+    }
+}
@@ -0,0 +1,9 @@
+public abstract class Shape {
+    protected static final int SIDES = 0;
+
+    private transient volatile int cache;
+
+    public abstract double area();
+
+    protected static synchronized native void draw();
+}
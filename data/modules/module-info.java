@@ -0,0 +1,8 @@
+open module com.example.app {
+    requires transitive java.sql;
+    requires static lombok;
+    exports com.example.api to com.example.client, com.example.other;
+    opens com.example.internal;
+    uses com.example.spi.Plugin;
+    provides com.example.spi.Plugin with com.example.impl.DefaultPlugin;
+}
@@ -0,0 +1 @@
+boolean empty = o instanceof String s && s.isEmpty();
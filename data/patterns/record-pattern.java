@@ -0,0 +1,5 @@
+String s = switch (o) {
+    case Point(var x, var y) when x > y -> "below";
+    case Line(Point(int x, int y), Point end) -> "line";
+    default -> "other";
+};
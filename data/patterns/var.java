@@ -0,0 +1 @@
+var list = new ArrayList<String>();
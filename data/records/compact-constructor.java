@@ -0,0 +1,9 @@
+public record Point(int x, int y) implements Shape {
+    public Point {
+        Objects.requireNonNull(x);
+    }
+
+    static Point origin() {
+        return new Point(0, 0);
+    }
+}
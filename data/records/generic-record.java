@@ -0,0 +1 @@
+record Pair<A, B>(A first, B second) { }
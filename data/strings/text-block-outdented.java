@@ -0,0 +1,8 @@
+class Text {
+    String text() {
+        return """
+Hello,
+  World!
+""";
+    }
+}
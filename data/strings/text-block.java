@@ -0,0 +1,6 @@
+class Text {
+    String text = """
+        Hello,
+          World!
+        """;
+}
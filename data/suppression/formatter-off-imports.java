@@ -0,0 +1,8 @@
+package com.example;
+
+// @formatter:off
+import java.util.Map;
+import java.util.List;
+// @formatter:on
+
+class Imports { }
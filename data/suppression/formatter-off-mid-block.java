@@ -0,0 +1,15 @@
+class Table {
+    void first() {
+        print();
+        /* @formatter:off */
+        print("| a   | b   |");
+        print("|-----|-----|");
+    }
+
+    void   second()   {
+        print("| 1   | 2   |");
+    }
+    // @formatter:on
+
+    void third() { }
+}
@@ -0,0 +1,10 @@
+class Modifiers {
+    // @formatter:off
+    final static public int[] M;
+    // @formatter:on
+
+    // lint-format-ignore
+    final static public int[] N;
+
+    final static public int[] O;
+}
@@ -0,0 +1,8 @@
+class A {
+    int x;
+}
+
+// @formatter:off
+class   B   {
+  int   y;
+}
@@ -0,0 +1,12 @@
+class Matrix {
+    // @formatter:off
+    int[][] identity = {
+        { 1, 0, 0 },
+        { 0, 1, 0 },
+        { 0, 0, 1 },
+    };
+    int[][]   zero   =   { { 0 } };
+    // @formatter:on
+
+    int[][] other;
+}
@@ -0,0 +1,7 @@
+class Aligned {
+    int x = 1;
+
+    // lint-format-ignore
+    int   longer    =   2;
+    int y = 3;
+}
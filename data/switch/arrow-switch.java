@@ -0,0 +1,7 @@
+int y = switch (x) {
+    case 1, 2 -> 3;
+    case 4 -> {
+        yield 5;
+    }
+    default -> throw new IllegalStateException();
+};
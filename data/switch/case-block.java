@@ -0,0 +1,7 @@
+switch (x) {
+    case 1: {
+        foo();
+        break;
+    }
+    default: { }
+}
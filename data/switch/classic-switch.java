@@ -0,0 +1,9 @@
+switch (x) {
+    case 1:
+    case 2:
+        foo();
+        break;
+
+    default:
+        bar();
+}
@@ -0,0 +1 @@
+switch (x) { }
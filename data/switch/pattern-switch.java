@@ -0,0 +1,5 @@
+String s = switch (o) {
+    case Integer i when i > 0 -> "positive";
+    case String text -> text;
+    case null, default -> "other";
+};
@@ -0,0 +1,4 @@
+class A {
+    String myReallyReallyReallyLongName =
+        myReallyReallyReallyReallyReallyReallyReallyLongStupidNamexxxxxxxx;
+}
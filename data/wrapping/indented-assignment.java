@@ -0,0 +1,6 @@
+class A {
+    void a() {
+        String myReallyReallyReallyLongName =
+            myReallyReallyReallyReallyReallyReallyReallyLongStupidNamexxxx;
+    }
+}
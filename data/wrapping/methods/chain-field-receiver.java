@@ -0,0 +1,4 @@
+this.items.stream()
+    .map(item -> item.toString())
+    .filter(value -> !value.isEmpty())
+    .forEach(System.out::println);
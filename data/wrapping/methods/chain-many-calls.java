@@ -0,0 +1,7 @@
+Request request = Request.builder()
+    .method("GET")
+    .uri(uri)
+    .header("Accept", "json")
+    .timeout(duration)
+    .retries(3)
+    .build();
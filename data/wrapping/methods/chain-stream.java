@@ -0,0 +1,4 @@
+List<String> names = people.stream()
+    .filter(person -> person.isActive())
+    .map(Person::getName)
+    .collect(Collectors.toList());
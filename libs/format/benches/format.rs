@@ -0,0 +1,270 @@
+//! Benchmarks for each stage of the formatter: parse, transform and render, plus end to end throughput over a directory
+//!
+//! Run with `cargo bench -p format`. Set `BENCH_CORPUS` to benchmark a directory other than the golden files in `data/`.
+//! Results are kept in `target/bench-results/format.tsv` and each run is compared against the previous one.
+
+use std::{
+    env,
+    ffi::OsStr,
+    fs::{create_dir_all, read_to_string, write},
+    hint::black_box,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use format::{
+    options::FormatOptions,
+    print::print,
+    render::{PrettyPrintParameters, WrapParameters, prettyprint},
+    transform::{FormatRules, transform},
+};
+use parser::{language::Language, parser::Parser};
+use walkdir::WalkDir;
+
+const WARMUP: Duration = Duration::from_millis(200);
+const MEASURE: Duration = Duration::from_secs(2);
+const MAX_ITERATIONS: usize = 10_000;
+
+const PARAMETERS: PrettyPrintParameters = PrettyPrintParameters {
+    indent_size: 4,
+    max_line_length: 100,
+};
+
+struct Measurement {
+    name: String,
+    iterations: usize,
+    median: Duration,
+    min: Duration,
+    bytes: usize,
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`, any other argument filters the benchmarks by name
+    let filter = env::args().skip(1).find(|arg| !arg.starts_with("--"));
+
+    let inputs = [
+        (
+            "small",
+            read_to_string(data_dir().join("classes/class-with-mixed-members.java")).unwrap(),
+        ),
+        ("large", large_input(10_000)),
+        ("nested-blocks", nested_blocks_input(40)),
+        ("nested-calls", nested_calls_input(8)),
+    ];
+
+    let rules = FormatRules::of(Language::Java);
+    let options = FormatOptions::default();
+    let mut parser = Parser::of(Language::Java);
+
+    let mut measurements = Vec::new();
+
+    let mut run = |name: String, bytes: usize, routine: &mut dyn FnMut()| {
+        if filter
+            .as_ref()
+            .is_none_or(|filter| name.contains(filter.as_str()))
+        {
+            let measurement = measure(name, bytes, routine);
+            report(&measurement);
+            measurements.push(measurement);
+        }
+    };
+
+    for (input_name, source) in &inputs {
+        let bytes = source.len();
+
+        run(format!("parse/{input_name}"), bytes, &mut || {
+            black_box(parser.parse(black_box(source)));
+        });
+
+        let tree = parser.parse(source).unwrap();
+
+        run(format!("transform/{input_name}"), bytes, &mut || {
+            let directives = rules.directives(&tree);
+            black_box(transform(black_box(&tree), &rules, &options, &directives));
+        });
+
+        let formatted = transform(&tree, &rules, &options, &rules.directives(&tree));
+
+        run(format!("render/{input_name}"), bytes, &mut || {
+            black_box(prettyprint(
+                black_box(&formatted),
+                &PARAMETERS,
+                WrapParameters::default(),
+            ));
+        });
+    }
+
+    let corpus = corpus();
+    let corpus_bytes = corpus.iter().map(String::len).sum();
+
+    run(
+        format!("end-to-end/{} files", corpus.len()),
+        corpus_bytes,
+        &mut || {
+            for source in &corpus {
+                let tree = parser.parse(source).unwrap();
+                black_box(print(&tree, &rules, &options, &PARAMETERS));
+            }
+        },
+    );
+
+    compare_with_previous(&measurements);
+}
+
+fn measure(name: String, bytes: usize, routine: &mut dyn FnMut()) -> Measurement {
+    let warmup_start = Instant::now();
+    while warmup_start.elapsed() < WARMUP {
+        routine();
+    }
+
+    let mut samples = Vec::new();
+    let measure_start = Instant::now();
+    while measure_start.elapsed() < MEASURE && samples.len() < MAX_ITERATIONS {
+        let start = Instant::now();
+        routine();
+        samples.push(start.elapsed());
+    }
+
+    samples.sort();
+
+    Measurement {
+        name,
+        iterations: samples.len(),
+        median: samples[samples.len() / 2],
+        min: samples[0],
+        bytes,
+    }
+}
+
+fn report(measurement: &Measurement) {
+    let throughput = measurement.bytes as f64 / measurement.median.as_secs_f64() / 1_000_000.0;
+
+    println!(
+        "{:<40} median {:>12?}  min {:>12?}  {:>8.2} MB/s  ({} iterations)",
+        measurement.name, measurement.median, measurement.min, throughput, measurement.iterations
+    );
+}
+
+fn compare_with_previous(measurements: &[Measurement]) {
+    let directory = env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target"))
+        .join("bench-results");
+    let path = directory.join("format.tsv");
+
+    let previous = read_to_string(&path).unwrap_or_default();
+
+    println!();
+
+    for measurement in measurements {
+        let previous_median = previous
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .find(|(name, _)| *name == measurement.name)
+            .and_then(|(_, nanos)| nanos.parse::<f64>().ok());
+
+        if let Some(previous_median) = previous_median {
+            let change = (measurement.median.as_nanos() as f64 / previous_median - 1.0) * 100.0;
+            println!(
+                "{:<40} {:>+8.1}% compared to previous run",
+                measurement.name, change
+            );
+        }
+    }
+
+    // Keep entries for benchmarks that were filtered out of this run
+    let mut lines: Vec<String> = previous
+        .lines()
+        .filter(|line| {
+            !measurements
+                .iter()
+                .any(|measurement| line.split('\t').next() == Some(measurement.name.as_str()))
+        })
+        .map(str::to_owned)
+        .collect();
+    lines.extend(
+        measurements
+            .iter()
+            .map(|measurement| format!("{}\t{}", measurement.name, measurement.median.as_nanos())),
+    );
+
+    create_dir_all(&directory).unwrap();
+    write(&path, lines.join("\n") + "\n").unwrap();
+}
+
+fn data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data")
+}
+
+fn corpus() -> Vec<String> {
+    let directory = env::var_os("BENCH_CORPUS")
+        .map(PathBuf::from)
+        .unwrap_or_else(data_dir);
+
+    WalkDir::new(directory)
+        .sort_by_file_name()
+        .into_iter()
+        .map(Result::unwrap)
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| entry.path().extension().and_then(OsStr::to_str) == Some("java"))
+        .map(|entry| read_to_string(entry.path()).unwrap())
+        .collect()
+}
+
+/** A class with enough members to reach the given number of lines */
+fn large_input(lines: usize) -> String {
+    let mut source = String::from("package bench;\n\nimport java.util.List;\n\nclass Large {\n");
+
+    let mut index = 0;
+    while source.lines().count() < lines {
+        source += &format!(
+            "    private final int field{index} = {index};
+
+    public int method{index}(int first, List<String> second) throws Exception {{
+        int value = first + field{index} * 2 - second.size();
+        if (value > {index}) {{
+            second.add(String.valueOf(value)).trim();
+        }} else {{
+            value = first > 0 ? first : -first;
+        }}
+        for (int i = 0; i < value; i++) {{
+            System.out.println(second.get(i % second.size()) + \" at \" + i);
+        }}
+        return value;
+    }}
+
+"
+        );
+        index += 1;
+    }
+
+    source + "}\n"
+}
+
+/** Blocks nested to the given depth */
+fn nested_blocks_input(depth: usize) -> String {
+    let mut source = String::new();
+
+    for level in 0..depth {
+        source += &format!("{}if (condition{level}) {{\n", "    ".repeat(level));
+    }
+    source += &format!("{}statement();\n", "    ".repeat(depth));
+    for level in (0..depth).rev() {
+        source += &format!("{}}}\n", "    ".repeat(level));
+    }
+
+    source
+}
+
+/** Method calls nested to the given depth, each too long to fit on a line */
+fn nested_calls_input(depth: usize) -> String {
+    let mut source = String::from("result = ");
+
+    for level in 0..depth {
+        source += &format!("someReallyLongFunctionName{level}(someReallyLongArgumentName{level}, ");
+    }
+    source += "innermost";
+    source += &")".repeat(depth);
+
+    source + ";\n"
+}
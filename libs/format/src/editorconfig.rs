@@ -1,20 +1,29 @@
 use std::{collections::HashMap, ffi::OsString, fs::read_to_string};
 
+use crate::{
+    encoding::LineEnding,
+    options::{BlankLinePolicy, BraceStyle, FormatOptions},
+    render::PrettyPrintParameters,
+};
+
 // TODO new crate
 
-pub(crate) struct EditorConfigResolver {
+#[derive(Default)]
+pub struct EditorConfigResolver {
     cache: HashMap<OsString, EditorConfig>,
 }
 
 impl EditorConfigResolver {
-    pub(crate) fn new() -> Self {
-        Self { cache: HashMap::new() }
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+        }
     }
 
     /**
      * Expects absolute paths
      */
-    pub(crate) fn resolve(&mut self, path: &std::path::Path) -> EditorConfigSettings {
+    pub fn resolve(&mut self, path: &std::path::Path) -> EditorConfigSettings {
         // ancestors is a list of ancestors i.e. [./path/subpath, ./path, ./]
         let ancestors: Vec<&std::path::Path> = path.ancestors().skip(1).collect();
 
@@ -57,10 +66,16 @@ impl EditorConfigResolver {
 
         // Now we iterate the ancestors in reverse and resolve
         // TODO if we immediately get a cache hit...
-        for current_path in ancestors.iter().rev().skip(ancestors.len() - first_uncached_index) {
-            let current = &parsed[current_path];
-
-            let combined = EditorConfig::combine(&previous, current);
+        for current_path in ancestors
+            .iter()
+            .rev()
+            .skip(ancestors.len() - first_uncached_index)
+        {
+            // Directories without an .editorconfig take on that of their parent
+            let combined = match parsed.get(current_path) {
+                Some(current) => EditorConfig::combine(&previous, current),
+                None => previous.clone(),
+            };
 
             self.cache
                 .insert(current_path.as_os_str().to_os_string(), combined.clone());
@@ -121,7 +136,7 @@ fn parse_key_value<'lifetime>(
 
 // TODO do we need clone?
 #[derive(Default, Clone)]
-pub(crate) struct EditorConfig {
+pub struct EditorConfig {
     root: bool,
     blocks: Vec<(String, EditorConfigSettings)>,
 }
@@ -150,17 +165,67 @@ impl EditorConfig {
 }
 
 #[derive(Default, Debug, Clone)]
-pub(crate) struct EditorConfigSettings {
-    pub(crate) indent_size: Option<usize>,
+pub struct EditorConfigSettings {
+    pub indent_size: Option<usize>,
+    pub end_of_line: Option<LineEnding>,
+    /** Comma separated, see ImportLayout */
+    pub import_groups: Option<Vec<String>>,
+    pub max_chain_calls: Option<usize>,
+    pub brace_style: Option<BraceStyle>,
+    pub max_blank_lines: Option<usize>,
+    pub blank_line_after_class_header: Option<BlankLinePolicy>,
+    pub blank_line_before_closing_brace: Option<BlankLinePolicy>,
+    pub blank_line_after_control_statements: Option<bool>,
 }
 
 impl EditorConfigSettings {
+    pub fn pretty_print_parameters(&self) -> PrettyPrintParameters {
+        PrettyPrintParameters {
+            indent_size: self.indent_size.unwrap_or(4),
+            max_line_length: 100,
+        }
+    }
+
+    /** The options set, with the defaults for the rest */
+    pub fn format_options(&self) -> FormatOptions {
+        let defaults = FormatOptions::default();
+
+        FormatOptions {
+            max_chain_calls: self.max_chain_calls.unwrap_or(defaults.max_chain_calls),
+            brace_style: self.brace_style.unwrap_or(defaults.brace_style),
+            max_blank_lines: self.max_blank_lines.unwrap_or(defaults.max_blank_lines),
+            blank_line_after_class_header: self
+                .blank_line_after_class_header
+                .unwrap_or(defaults.blank_line_after_class_header),
+            blank_line_before_closing_brace: self
+                .blank_line_before_closing_brace
+                .unwrap_or(defaults.blank_line_before_closing_brace),
+            blank_line_after_control_statements: self
+                .blank_line_after_control_statements
+                .unwrap_or(defaults.blank_line_after_control_statements),
+        }
+    }
+
     fn combine(
         first: &EditorConfigSettings,
         second: &EditorConfigSettings,
     ) -> EditorConfigSettings {
-        EditorConfigSettings { 
-            indent_size: second.indent_size.or(first.indent_size)
+        EditorConfigSettings {
+            indent_size: second.indent_size.or(first.indent_size),
+            end_of_line: second.end_of_line.or(first.end_of_line),
+            import_groups: second.import_groups.clone().or(first.import_groups.clone()),
+            max_chain_calls: second.max_chain_calls.or(first.max_chain_calls),
+            brace_style: second.brace_style.or(first.brace_style),
+            max_blank_lines: second.max_blank_lines.or(first.max_blank_lines),
+            blank_line_after_class_header: second
+                .blank_line_after_class_header
+                .or(first.blank_line_after_class_header),
+            blank_line_before_closing_brace: second
+                .blank_line_before_closing_brace
+                .or(first.blank_line_before_closing_brace),
+            blank_line_after_control_statements: second
+                .blank_line_after_control_statements
+                .or(first.blank_line_after_control_statements),
         }
     }
 }
@@ -169,6 +234,33 @@ impl From<HashMap<&str, &str>> for EditorConfigSettings {
     fn from(map: HashMap<&str, &str>) -> Self {
         EditorConfigSettings {
             indent_size: map.get("indent_size").and_then(|int| (**int).parse().ok()),
+            end_of_line: map
+                .get("end_of_line")
+                .and_then(|ending| ending.parse().ok()),
+            import_groups: map.get("java_import_groups").map(|groups| {
+                groups
+                    .split(',')
+                    .map(|group| group.trim().to_owned())
+                    .collect()
+            }),
+            max_chain_calls: map
+                .get("java_max_chain_calls")
+                .and_then(|int| (**int).parse().ok()),
+            brace_style: map
+                .get("java_brace_style")
+                .and_then(|style| style.parse().ok()),
+            max_blank_lines: map
+                .get("java_max_blank_lines")
+                .and_then(|int| (**int).parse().ok()),
+            blank_line_after_class_header: map
+                .get("java_blank_line_after_class_header")
+                .and_then(|policy| policy.parse().ok()),
+            blank_line_before_closing_brace: map
+                .get("java_blank_line_before_closing_brace")
+                .and_then(|policy| policy.parse().ok()),
+            blank_line_after_control_statements: map
+                .get("java_blank_line_after_control_statements")
+                .and_then(|bool| (**bool).parse().ok()),
         }
     }
 }
@@ -0,0 +1,135 @@
+use std::str::FromStr;
+
+const BOM: char = '\u{feff}';
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+    Cr,
+}
+
+impl LineEnding {
+    /** The first line ending of the source, or Lf if it has a single line */
+    pub fn detect(source: &str) -> LineEnding {
+        match source.find(['\r', '\n']) {
+            Some(index) if source[index..].starts_with("\r\n") => LineEnding::CrLf,
+            Some(index) if source[index..].starts_with('\r') => LineEnding::Cr,
+            _ => LineEnding::Lf,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+}
+
+/** Accepts the values of the EditorConfig `end_of_line` property */
+impl FromStr for LineEnding {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "lf" => Ok(LineEnding::Lf),
+            "crlf" => Ok(LineEnding::CrLf),
+            "cr" => Ok(LineEnding::Cr),
+            _ => Err(()),
+        }
+    }
+}
+
+/**
+ * How a source file was written, so it can be formatted as `\n` separated text and written back the same way
+ *
+ * The BOM would otherwise be treated as content by the parser
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Encoding {
+    pub line_ending: LineEnding,
+    pub bom: bool,
+}
+
+impl Encoding {
+    pub fn detect(source: &str) -> Encoding {
+        Encoding {
+            line_ending: LineEnding::detect(source),
+            bom: source.starts_with(BOM),
+        }
+    }
+
+    /** The source without a BOM and with `\n` line endings */
+    pub fn normalize(source: &str) -> String {
+        source
+            .strip_prefix(BOM)
+            .unwrap_or(source)
+            .replace("\r\n", "\n")
+            .replace('\r', "\n")
+    }
+
+    /** The `\n` separated output, with the line ending and BOM of the encoding */
+    pub fn restore(&self, formatted: &str) -> String {
+        let content = match self.line_ending {
+            LineEnding::Lf => formatted.to_owned(),
+            line_ending => formatted.replace('\n', line_ending.as_str()),
+        };
+
+        if self.bom {
+            format!("{BOM}{content}")
+        } else {
+            content
+        }
+    }
+
+    /** The offset within the normalized source of a byte offset within the original source */
+    pub fn normalized_offset(source: &str, offset: usize) -> usize {
+        let offset = offset.min(source.len());
+        let bom = if source.starts_with(BOM) {
+            BOM.len_utf8()
+        } else {
+            0
+        };
+
+        // Each `\r\n` loses its `\r`, where a lone `\r` is replaced in place
+        let removed = source.as_bytes()[..offset]
+            .windows(2)
+            .filter(|pair| pair == b"\r\n")
+            .count();
+
+        offset.saturating_sub(bom).saturating_sub(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restores_crlf_and_bom() {
+        let source = "\u{feff}class A {\r\n}\r\n";
+        let encoding = Encoding::detect(source);
+
+        assert_eq!(
+            encoding,
+            Encoding {
+                line_ending: LineEnding::CrLf,
+                bom: true
+            }
+        );
+        assert_eq!(Encoding::normalize(source), "class A {\n}\n");
+        assert_eq!(encoding.restore(&Encoding::normalize(source)), source);
+    }
+
+    #[test]
+    fn maps_offsets_past_removed_characters() {
+        let source = "\u{feff}a\r\nb\r\nc";
+
+        assert_eq!(
+            Encoding::normalized_offset(source, source.find('c').unwrap()),
+            4
+        );
+    }
+}
@@ -9,6 +9,12 @@ pub enum FormatNode {
     Space,
     // WrapBoundary(Box<FormatNode>), // do we need aside from indent?
     Newline,
+    /** Content which ends its line, forcing the enclosing group to wrap, e.g. a trailing line comment */
+    LineSuffix(Box<str>),
+    /** Javadoc comment, normalized to the width available once indented */
+    Javadoc(Box<str>),
+    /** Text block, whose lines after the first are part of the string and so are never indented */
+    TextBlock(Box<str>),
     // Empty,
 }
 
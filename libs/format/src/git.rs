@@ -0,0 +1,92 @@
+use std::{ops::RangeInclusive, path::Path, process::Command};
+
+/**
+ * Lines of the file which differ from the given revision, as 1-based, inclusive line ranges
+ *
+ * Files not tracked by git, whether untracked or ignored, are considered changed in their entirety
+ */
+pub fn changed_lines(path: &Path, revision: &str) -> Result<Vec<RangeInclusive<usize>>, String> {
+    let tracked = git(path, &["ls-files", "--"])?;
+
+    if tracked.trim().is_empty() {
+        return Ok(vec![1..=usize::MAX]);
+    }
+
+    let diff = git(
+        path,
+        &[
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            revision,
+            "--",
+        ],
+    )?;
+
+    Ok(parse_hunks(&diff))
+}
+
+fn git(path: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .arg(path)
+        .current_dir(path.parent().unwrap_or(Path::new(".")))
+        .output()
+        .map_err(|error| format!("Failed to run git: {error}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|error| error.to_string())
+}
+
+/** Reads the added side of each hunk header, i.e. `+start,count` from `@@ -1,2 +3,4 @@` */
+fn parse_hunks(diff: &str) -> Vec<RangeInclusive<usize>> {
+    diff.lines()
+        .filter_map(|line| line.strip_prefix("@@ "))
+        .filter_map(|header| header.split(' ').find_map(|part| part.strip_prefix('+')))
+        .filter_map(|added| {
+            let (start, count) = match added.split_once(',') {
+                Some((start, count)) => {
+                    (start.parse::<usize>().ok()?, count.parse::<usize>().ok()?)
+                }
+                None => (added.parse::<usize>().ok()?, 1),
+            };
+
+            // Pure deletions leave no lines behind to format
+            (count > 0).then(|| start..=start + count - 1)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_added_lines_from_hunk_headers() {
+        let diff = "diff --git a/A.java b/A.java
+index 1111111..2222222 100644
+--- a/A.java
++++ b/A.java
+@@ -3 +3 @@ class A {
+-    int  x;
++    int  y;
+@@ -10,0 +11,2 @@ class A {
++    int a;
++    int b;
+@@ -20,3 +22,0 @@ class A {
+-    int c;
+-    int d;
+-    int e;
+";
+
+        assert_eq!(parse_hunks(diff), vec![3..=3, 11..=12]);
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::HashSet;
+
+use parser::tree::Tree;
+
+use crate::rewrite::Edit;
+
+/**
+ * Groups imports are laid out in, each separated by a blank line
+ *
+ * Each group is a package prefix such as `java.`, `*` for imports matching no other group, or either prefixed with
+ * `static` for static imports
+ */
+pub struct ImportLayout {
+    groups: Vec<ImportGroup>,
+}
+
+struct ImportGroup {
+    is_static: bool,
+    /** None matches any import not matched by another group */
+    prefix: Option<String>,
+}
+
+impl ImportLayout {
+    pub fn of<S: AsRef<str>>(groups: &[S]) -> ImportLayout {
+        ImportLayout {
+            groups: groups
+                .iter()
+                .map(|group| {
+                    let group = group.as_ref().trim();
+
+                    let (is_static, prefix) = match group.strip_prefix("static") {
+                        Some(rest) => (true, rest.trim()),
+                        None => (false, group),
+                    };
+
+                    ImportGroup {
+                        is_static,
+                        prefix: (!prefix.is_empty() && prefix != "*").then(|| prefix.to_owned()),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /** Index of the group with the longest prefix matching the import, falling back to the catch-all groups */
+    fn group_of(&self, import: &Import) -> usize {
+        let candidates = || {
+            self.groups
+                .iter()
+                .enumerate()
+                .filter(|(_, group)| group.is_static == import.is_static)
+        };
+
+        candidates()
+            .filter_map(|(index, group)| {
+                group
+                    .prefix
+                    .as_ref()
+                    .filter(|prefix| import.path.starts_with(prefix.as_str()))
+                    .map(|prefix| (index, prefix.len()))
+            })
+            .max_by_key(|(_, length)| *length)
+            .map(|(index, _)| index)
+            .or_else(|| {
+                candidates()
+                    .find(|(_, group)| group.prefix.is_none())
+                    .map(|(index, _)| index)
+            })
+            // Imports matching no group go last
+            .unwrap_or(self.groups.len())
+    }
+}
+
+impl Default for ImportLayout {
+    fn default() -> Self {
+        ImportLayout::of(&["java.", "javax.", "*", "static"])
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct Import {
+    is_static: bool,
+    /** Dotted name, including any trailing `.*` */
+    path: String,
+}
+
+impl Import {
+    fn of(declaration: &Tree) -> Import {
+        let mut path = String::new();
+        let mut is_static = false;
+
+        for child in declaration.children() {
+            match child.name() {
+                "import" | ";" => {}
+                "static" => is_static = true,
+                _ => leaves(child, &mut |leaf| path.push_str(leaf.text())),
+            }
+        }
+
+        Import { is_static, path }
+    }
+
+    /** The name the import brings into scope, unless it is on demand */
+    fn simple_name(&self) -> Option<&str> {
+        if self.path.ends_with('*') {
+            return None;
+        }
+
+        self.path.rsplit('.').next()
+    }
+
+    fn declaration(&self) -> String {
+        if self.is_static {
+            format!("import static {};", self.path)
+        } else {
+            format!("import {};", self.path)
+        }
+    }
+}
+
+/**
+ * Sorts, groups and deduplicates the imports of the program, optionally removing imports which are never used
+ *
+ * Returns None if the imports are already organized or cannot be rewritten safely
+ */
+pub fn organize_imports(
+    program: &Tree,
+    layout: &ImportLayout,
+    remove_unused: bool,
+) -> Option<Edit> {
+    let children = program.children();
+
+    let first = children
+        .iter()
+        .position(|child| child.name() == "import_declaration")?;
+    let last = children
+        .iter()
+        .rposition(|child| child.name() == "import_declaration")?;
+
+    // Comments among the imports have nowhere to go
+    if children[first..=last]
+        .iter()
+        .any(|child| child.name() != "import_declaration")
+    {
+        return None;
+    }
+
+    let used = if remove_unused {
+        Some(used_names(program))
+    } else {
+        None
+    };
+
+    let mut seen = HashSet::new();
+    let mut groups: Vec<Vec<Import>> = Vec::new();
+    groups.resize_with(layout.groups.len() + 1, Vec::new);
+
+    for import in children[first..=last].iter().map(Import::of) {
+        let is_used = match (&used, import.simple_name()) {
+            (Some(used), Some(name)) => used.contains(name),
+            _ => true,
+        };
+
+        if is_used && seen.insert(import.clone()) {
+            groups[layout.group_of(&import)].push(import);
+        }
+    }
+
+    let organized = groups
+        .iter_mut()
+        .filter(|group| !group.is_empty())
+        .map(|group| {
+            group.sort_by(|first, second| first.path.cmp(&second.path));
+
+            group
+                .iter()
+                .map(Import::declaration)
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n");
+
+    let source = program.source();
+    let range = children[first].range().start_byte..children[last].range().end_byte;
+
+    if source[range.clone()] == organized {
+        return None;
+    }
+
+    if organized.is_empty() {
+        // Also drop the line the imports were on
+        let end = source[range.end..]
+            .find(|character: char| !character.is_whitespace())
+            .map_or(source.len(), |offset| range.end + offset);
+
+        return Some(Edit {
+            range: range.start..end,
+            replacement: organized,
+        });
+    }
+
+    Some(Edit {
+        range,
+        replacement: organized,
+    })
+}
+
+/** Identifiers referenced outside of the package and import declarations, including from comments */
+fn used_names<'tree>(program: &'tree Tree) -> HashSet<&'tree str> {
+    let mut used = HashSet::new();
+
+    for child in program.children() {
+        if child.name() == "import_declaration" || child.name() == "package_declaration" {
+            continue;
+        }
+
+        leaves(child, &mut |leaf| match leaf.name() {
+            "identifier" | "type_identifier" => {
+                used.insert(leaf.text());
+            }
+            // Javadoc can refer to imported types, e.g. through {@link}
+            "line_comment" | "block_comment" => {
+                used.extend(
+                    leaf.text()
+                        .split(|character: char| {
+                            !character.is_alphanumeric() && character != '_' && character != '$'
+                        })
+                        .filter(|word| !word.is_empty()),
+                );
+            }
+            _ => {}
+        });
+    }
+
+    used
+}
+
+fn leaves<'tree, 'source>(
+    node: &'tree Tree<'source>,
+    visit: &mut impl FnMut(&'tree Tree<'source>),
+) {
+    if node.children().is_empty() {
+        visit(node);
+    }
+
+    for child in node.children() {
+        leaves(child, visit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::{language::Language, parser::Parser};
+
+    use super::*;
+    use crate::rewrite::apply;
+
+    fn organize(source: &str, layout: &ImportLayout, remove_unused: bool) -> String {
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+
+        apply(source, organize_imports(&tree, layout, remove_unused))
+            .unwrap_or_else(|| source.to_owned())
+    }
+
+    #[test]
+    fn sorts_and_groups() {
+        let source = "import static java.util.Objects.requireNonNull;\nimport com.example.B;\nimport java.util.Map;\nimport javax.inject.Inject;\nimport java.util.List;\nimport com.example.A;\n\nclass X { }\n";
+
+        assert_eq!(
+            organize(source, &ImportLayout::default(), false),
+            "import java.util.List;\nimport java.util.Map;\n\nimport javax.inject.Inject;\n\nimport com.example.A;\nimport com.example.B;\n\nimport static java.util.Objects.requireNonNull;\n\nclass X { }\n"
+        );
+    }
+
+    #[test]
+    fn uses_longest_matching_prefix() {
+        let layout = ImportLayout::of(&["*", "com.", "com.example."]);
+        let source = "import com.example.A;\nimport com.other.B;\nimport org.C;\n";
+
+        assert_eq!(
+            organize(source, &layout, false),
+            "import org.C;\n\nimport com.other.B;\n\nimport com.example.A;\n"
+        );
+    }
+
+    #[test]
+    fn removes_duplicates() {
+        let source = "import a.A;\nimport a.A;\n";
+
+        assert_eq!(
+            organize(source, &ImportLayout::default(), false),
+            "import a.A;\n"
+        );
+    }
+
+    #[test]
+    fn removes_unused_when_asked() {
+        let source = "import a.Unused;\nimport a.Used;\nimport a.Linked;\nimport b.*;\n\n/** {@link Linked} */\nclass X { Used used; }\n";
+
+        assert_eq!(
+            organize(source, &ImportLayout::default(), true),
+            "import a.Linked;\nimport a.Used;\nimport b.*;\n\n/** {@link Linked} */\nclass X { Used used; }\n"
+        );
+    }
+
+    #[test]
+    fn leaves_imports_with_comments() {
+        let source = "import b.B;\n// keep\nimport a.A;\n";
+
+        assert_eq!(organize(source, &ImportLayout::default(), false), source);
+    }
+}
@@ -0,0 +1,363 @@
+/**
+ * Normalizes a Javadoc comment to fit within the width
+ *
+ * Leading `*`s are aligned, prose is re-wrapped, `<pre>` and multi-line `{@code}` blocks are kept as written,
+ * and block tags are ordered and aligned after a blank line
+ */
+pub fn format(comment: &str, width: usize) -> String {
+    let Some(inner) = comment
+        .strip_prefix("/**")
+        .and_then(|inner| inner.strip_suffix("*/"))
+    else {
+        return comment.to_owned();
+    };
+
+    let lines: Vec<&str> = inner.split('\n').map(strip_margin).collect();
+
+    // Nothing to normalize
+    if lines.iter().all(|line| line.trim().is_empty()) {
+        return comment.to_owned();
+    }
+
+    let Javadoc {
+        description,
+        mut tags,
+    } = parse(&lines);
+
+    tags.sort_by_key(|tag| tag_order(&tag.name));
+
+    let text_width = width.saturating_sub(" * ".len()).max(1);
+
+    if !comment.contains('\n') {
+        let content = match (&description[..], &tags[..]) {
+            ([Block::Prose(text)], []) => Some(words(text).join(" ")),
+            ([], [tag]) => Some(format_tag(tag, &tags, usize::MAX).join(" ")),
+            _ => None,
+        };
+
+        if let Some(content) = content {
+            let single_line = format!("/** {content} */");
+
+            if single_line.len() <= width {
+                return single_line;
+            }
+        }
+    }
+
+    let mut output: Vec<String> = vec![];
+
+    for block in &description {
+        match block {
+            Block::Prose(text) => output.extend(fill(&words(text), text_width, 0)),
+            Block::Verbatim(lines) => output.extend(lines.iter().cloned()),
+            Block::Blank => output.push(String::new()),
+        }
+    }
+
+    if !tags.is_empty() && !output.is_empty() {
+        output.push(String::new());
+    }
+
+    for tag in &tags {
+        output.extend(format_tag(tag, &tags, text_width));
+    }
+
+    std::iter::once("/**".to_owned())
+        .chain(output.iter().map(|line| {
+            if line.is_empty() {
+                " *".to_owned()
+            } else {
+                format!(" * {line}")
+            }
+        }))
+        .chain(std::iter::once(" */".to_owned()))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+struct Javadoc {
+    description: Vec<Block>,
+    tags: Vec<Tag>,
+}
+
+enum Block {
+    Prose(String),
+    Verbatim(Vec<String>),
+    Blank,
+}
+
+struct Tag {
+    name: String,
+    /** The parameter or exception the tag describes */
+    argument: Option<String>,
+    text: String,
+}
+
+/** Strips the indentation and `*` margin from a line, keeping any further indentation */
+fn strip_margin(line: &str) -> &str {
+    let line = line.trim_start();
+    let line = line.strip_prefix('*').unwrap_or(line);
+
+    line.strip_prefix(' ').unwrap_or(line).trim_end()
+}
+
+fn parse(lines: &[&str]) -> Javadoc {
+    let mut description: Vec<Block> = vec![];
+    let mut tags: Vec<Tag> = vec![];
+
+    let mut lines = lines.iter();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if starts_verbatim(trimmed) {
+            let mut verbatim = vec![line.to_string()];
+            let mut depth = brace_depth(line);
+            let mut open = trimmed.contains("<pre") && !trimmed.contains("</pre>") || depth > 0;
+
+            while open && let Some(line) = lines.next() {
+                verbatim.push(line.to_string());
+                depth += brace_depth(line);
+                open = if verbatim[0].contains("<pre") {
+                    !line.contains("</pre>")
+                } else {
+                    depth > 0
+                };
+            }
+
+            // An unclosed block runs to the end of the comment, past its closing blank line
+            while verbatim.len() > 1 && verbatim.last().is_some_and(|line| line.trim().is_empty()) {
+                verbatim.pop();
+            }
+
+            description.push(Block::Verbatim(verbatim));
+        } else if is_tag(trimmed) {
+            let (name, rest) = split_word(&trimmed[1..]);
+
+            let (argument, text) = if takes_argument(name) {
+                let (argument, text) = split_word(rest);
+                (Some(argument.to_owned()), text)
+            } else {
+                (None, rest)
+            };
+
+            tags.push(Tag {
+                name: name.to_owned(),
+                argument,
+                text: text.to_owned(),
+            });
+        } else if let Some(tag) = tags.last_mut() {
+            // Blank lines within the tag block are dropped
+            if !trimmed.is_empty() {
+                tag.text = (tag.text.clone() + " " + trimmed).trim().to_owned();
+            }
+        } else if trimmed.is_empty() {
+            if !matches!(description.last(), None | Some(Block::Blank)) {
+                description.push(Block::Blank);
+            }
+        } else {
+            match description.last_mut() {
+                // HTML elements start on their own line
+                Some(Block::Prose(text)) if !trimmed.starts_with('<') => {
+                    text.push(' ');
+                    text.push_str(trimmed);
+                }
+                _ => description.push(Block::Prose(trimmed.to_owned())),
+            }
+        }
+    }
+
+    while matches!(description.last(), Some(Block::Blank)) {
+        description.pop();
+    }
+
+    Javadoc { description, tags }
+}
+
+fn starts_verbatim(line: &str) -> bool {
+    line.starts_with("<pre") || line.contains("{@code") && brace_depth(line) > 0
+}
+
+/** The number of braces the line leaves open */
+fn brace_depth(line: &str) -> isize {
+    line.chars()
+        .map(|character| match character {
+            '{' => 1,
+            '}' => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+fn is_tag(line: &str) -> bool {
+    line.strip_prefix('@')
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|character| character.is_ascii_lowercase())
+}
+
+fn takes_argument(name: &str) -> bool {
+    matches!(name, "param" | "throws" | "exception")
+}
+
+fn tag_order(name: &str) -> usize {
+    match name {
+        "param" => 0,
+        "return" => 1,
+        "throws" | "exception" => 2,
+        _ => 3,
+    }
+}
+
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+
+    match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim()),
+        None => (text, ""),
+    }
+}
+
+/** Aligns the descriptions of tags of the same kind, with following lines hanging under the description */
+fn format_tag(tag: &Tag, tags: &[Tag], width: usize) -> Vec<String> {
+    let argument_width = tags
+        .iter()
+        .filter(|other| tag_order(&other.name) == tag_order(&tag.name))
+        .filter_map(|other| other.argument.as_ref())
+        .map(|argument| argument.len())
+        .max();
+
+    let prefix = match (&tag.argument, argument_width) {
+        (Some(argument), Some(argument_width)) => {
+            format!("@{} {argument:argument_width$} ", tag.name)
+        }
+        _ => format!("@{} ", tag.name),
+    };
+
+    let mut lines = fill(&words(&tag.text), width, prefix.len());
+
+    match lines.first_mut() {
+        Some(first) => first.replace_range(..prefix.len(), &prefix),
+        None => lines.push(prefix),
+    }
+
+    lines
+        .iter()
+        .map(|line| line.trim_end().to_owned())
+        .collect()
+}
+
+/** Splits prose into words, keeping inline tags such as `{@code a b}` whole */
+fn words(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+
+    for character in text.chars() {
+        match character {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => {}
+        }
+
+        if character.is_whitespace() && depth <= 0 {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(character);
+        }
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+/** Greedily fills lines up to the width, indenting every line by the hanging indent */
+fn fill(words: &[String], width: usize, hanging: usize) -> Vec<String> {
+    let indent = " ".repeat(hanging);
+    let mut lines: Vec<String> = vec![];
+    let mut line = indent.clone();
+
+    for word in words {
+        if line.len() > hanging && line.len() + 1 + word.len() > width {
+            lines.push(std::mem::replace(&mut line, indent.clone()));
+        }
+
+        if line.len() > hanging {
+            line.push(' ');
+        }
+
+        line.push_str(word);
+    }
+
+    if line.len() > hanging {
+        lines.push(line);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_prose() {
+        assert_eq!(
+            format("/**\n * one two\n * three four five\n */", 16),
+            "/**\n * one two three\n * four five\n */"
+        );
+    }
+
+    #[test]
+    fn orders_and_aligns_tags() {
+        assert_eq!(
+            format(
+                "/**\n * Does things.\n * @throws IOException when it fails\n * @return the result\n * @param first one\n * @param second two\n */",
+                100
+            ),
+            "/**\n * Does things.\n *\n * @param first  one\n * @param second two\n * @return the result\n * @throws IOException when it fails\n */"
+        );
+    }
+
+    #[test]
+    fn keeps_code_blocks() {
+        let comment = "/**\n * Example:\n * <pre>{@code\n *   call(  x );\n * }</pre>\n */";
+
+        assert_eq!(format(comment, 100), comment);
+    }
+
+    #[test]
+    fn keeps_inline_code_whole() {
+        assert_eq!(
+            format("/**\n * Use {@code a b c} here\n */", 18),
+            "/**\n * Use\n * {@code a b c}\n * here\n */"
+        );
+    }
+
+    #[test]
+    fn keeps_single_line() {
+        assert_eq!(format("/**   Short.  */", 100), "/** Short. */");
+    }
+
+    #[test]
+    fn keeps_single_tag_on_single_line() {
+        assert_eq!(format("/** @param x   x */", 100), "/** @param x x */");
+    }
+
+    #[test]
+    fn formats_unclosed_code_blocks_idempotently() {
+        for comment in [
+            "/**\n * Example:\n * <pre>\n *   call(  x );\n */",
+            "/**\n * Example: {@code\n *   call(  x );\n */",
+        ] {
+            let once = format(comment, 100);
+
+            assert_eq!(once, comment);
+            assert_eq!(format(&once, 100), once);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+pub mod debug;
+pub mod editorconfig;
+pub mod encoding;
+pub mod format_node;
+pub mod git;
+pub mod imports;
+pub mod javadoc;
+pub mod modifiers;
+pub mod options;
+pub mod print;
+pub mod range;
+pub mod render;
+pub mod rewrite;
+pub mod transform;
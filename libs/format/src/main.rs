@@ -2,34 +2,121 @@ use std::{
     env::args,
     ffi::OsStr,
     fs::{read_to_string, write},
+    ops::{Range, RangeInclusive},
     path::{Path, absolute},
+    process::exit,
 };
 
 #[allow(unused_imports)]
-use debug::print_as_tree;
+use format::debug::print_as_tree;
 
-use editorconfig::EditorConfigResolver;
+use format::{
+    editorconfig::EditorConfigResolver,
+    encoding::{Encoding, LineEnding},
+    git::changed_lines,
+    imports::ImportLayout,
+    print::print,
+    range::{line_range, print_range, print_ranges},
+    rewrite::{RewriteOptions, rewrite},
+    transform::FormatRules,
+};
 use parser::{language::Language, parser::Parser};
-use print::print;
-use render::PrettyPrintParameters;
 use walkdir::WalkDir;
 
-mod debug;
-mod editorconfig;
-mod format_node;
-mod print;
-mod render;
-mod transform;
+const USAGE: &str = "Usage: format [--check] [--remove-unused-imports] [--end-of-line <lf|crlf|cr>] [--range <start>:<end> | --lines <first>-<last> | --changed-since <revision>] <path>...";
+
+struct Options {
+    paths: Vec<String>,
+    selection: Option<Selection>,
+    /** Report files which are not formatted instead of writing them */
+    check: bool,
+    /** Remove imports which are never referenced when organizing imports */
+    remove_unused_imports: bool,
+    /** Line ending to write, rather than the one each file already uses */
+    end_of_line: Option<LineEnding>,
+}
+
+/** Part of each file to format, rather than the whole file */
+enum Selection {
+    /** Byte offsets, end exclusive */
+    Bytes(Range<usize>),
+    /** 1-based line numbers, inclusive */
+    Lines(RangeInclusive<usize>),
+    /** Lines changed since the git revision */
+    ChangedSince(String),
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        paths: Vec::new(),
+        selection: None,
+        check: false,
+        remove_unused_imports: false,
+        end_of_line: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--range" => {
+                let value = args.next().ok_or("--range expects <start>:<end>")?;
+                let (start, end) =
+                    parse_pair(&value, ':').ok_or("--range expects <start>:<end>")?;
+                options.selection = Some(Selection::Bytes(start..end));
+            }
+            "--lines" => {
+                let value = args.next().ok_or("--lines expects <first>-<last>")?;
+                let (first, last) =
+                    parse_pair(&value, '-').ok_or("--lines expects <first>-<last>")?;
+                options.selection = Some(Selection::Lines(first..=last));
+            }
+            "--changed-since" => {
+                let revision = args
+                    .next()
+                    .ok_or("--changed-since expects a git revision")?;
+                options.selection = Some(Selection::ChangedSince(revision));
+            }
+            "--end-of-line" => {
+                let value = args.next().ok_or("--end-of-line expects lf, crlf or cr")?;
+                let line_ending = value
+                    .parse()
+                    .map_err(|_| "--end-of-line expects lf, crlf or cr")?;
+                options.end_of_line = Some(line_ending);
+            }
+            "--check" => options.check = true,
+            "--remove-unused-imports" => options.remove_unused_imports = true,
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {flag}")),
+            _ => options.paths.push(arg),
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_pair(value: &str, separator: char) -> Option<(usize, usize)> {
+    let (first, second) = value.split_once(separator)?;
+    let (first, second) = (first.parse().ok()?, second.parse().ok()?);
+
+    (first <= second).then_some((first, second))
+}
 
 fn main() {
     println!("Hello, world!");
 
+    let options = parse_args(args().skip(1)).unwrap_or_else(|message| {
+        eprintln!("{message}\n{USAGE}");
+        exit(2);
+    });
+
     let mut parser = Parser::of(Language::Java);
 
+    let rules = FormatRules::of(Language::Java);
+
     let mut editor_config_resolver: EditorConfigResolver = EditorConfigResolver::new();
 
-    for arg in args().skip(1) {
-        let path = absolute(Path::new(&arg)).unwrap();
+    let mut unformatted = 0;
+
+    for arg in &options.paths {
+        let path = absolute(Path::new(arg)).unwrap();
 
         for entry_option in WalkDir::new(path).sort_by_file_name() {
             let entry: walkdir::DirEntry = entry_option.unwrap();
@@ -40,34 +127,126 @@ fn main() {
             );
 
             if entry.file_type().is_file() {
-                if entry.path().extension().and_then(OsStr::to_str) == Some("java") {
-                    handle(&mut parser, &mut editor_config_resolver, entry.path());
+                if entry.path().extension().and_then(OsStr::to_str) == Some("java")
+                    && handle(
+                        &mut parser,
+                        &rules,
+                        &mut editor_config_resolver,
+                        &options,
+                        entry.path(),
+                    )
+                {
+                    unformatted += 1;
                 }
                 // TODO non-java files
             }
         }
     }
+
+    if options.check && unformatted > 0 {
+        eprintln!("{unformatted} file(s) are not formatted");
+        exit(1);
+    }
 }
 
-fn handle(parser: &mut Parser, editor_config_resolver: &mut EditorConfigResolver, path: &Path) {
+fn handle(
+    parser: &mut Parser,
+    rules: &FormatRules,
+    editor_config_resolver: &mut EditorConfigResolver,
+    options: &Options,
+    path: &Path,
+) -> bool {
     let editorconfig = editor_config_resolver.resolve(path);
 
     println!("Resolved to {:?}", editorconfig);
 
-    let source_code = read_to_string(&path).unwrap();
+    let original = read_to_string(&path).unwrap();
+
+    // Formatting works on `\n` separated text, with the line endings and BOM restored when writing
+    let detected = Encoding::detect(&original);
+    let encoding = Encoding {
+        line_ending: options
+            .end_of_line
+            .or(editorconfig.end_of_line)
+            .unwrap_or(detected.line_ending),
+        ..detected
+    };
+
+    let source_code = Encoding::normalize(&original);
 
     let tree = parser.parse(&source_code).unwrap();
 
+    // Rewrites move code around, so are only applied when formatting the whole file
+    let rewritten = match &options.selection {
+        None => {
+            let rewrite_options = RewriteOptions {
+                import_layout: editorconfig
+                    .import_groups
+                    .as_ref()
+                    .map_or_else(ImportLayout::default, |groups| ImportLayout::of(groups)),
+                remove_unused_imports: options.remove_unused_imports,
+            };
+
+            rewrite(&tree, rules, &rewrite_options)
+        }
+        Some(_) => None,
+    };
+
+    let tree = match &rewritten {
+        Some(rewritten) => parser.parse(rewritten).unwrap(),
+        None => tree,
+    };
+
     // TODO take as debug arg
     // print_as_tree(&tree, 0);
 
+    let arguments = editorconfig.pretty_print_parameters();
+    let format_options = editorconfig.format_options();
+
     // is this an issue for unicode characters outside ascii?
-    let formatted = print(&tree, &PrettyPrintParameters {
-        indent_size: editorconfig.indent_size.unwrap_or(4),
-        max_line_length: 100,
-    });
+    let formatted = match &options.selection {
+        None => print(&tree, rules, &format_options, &arguments),
+        Some(Selection::Bytes(range)) => {
+            let range = Encoding::normalized_offset(&original, range.start)
+                ..Encoding::normalized_offset(&original, range.end);
+
+            print_range(&tree, rules, &format_options, &arguments, range)
+        }
+        Some(Selection::Lines(lines)) => print_range(
+            &tree,
+            rules,
+            &format_options,
+            &arguments,
+            line_range(&source_code, lines.clone()),
+        ),
+        Some(Selection::ChangedSince(revision)) => {
+            let changed = changed_lines(path, revision).unwrap_or_else(|message| {
+                eprintln!("{message}");
+                exit(2);
+            });
+
+            let ranges: Vec<_> = changed
+                .into_iter()
+                .map(|lines| line_range(&source_code, lines))
+                .collect();
+
+            print_ranges(&tree, rules, &format_options, &arguments, &ranges)
+        }
+    };
 
-    write(&path, formatted).expect("Unable to write to file");
+    let formatted = encoding.restore(&formatted);
+
+    if formatted == original {
+        return false;
+    }
+
+    if options.check {
+        println!("Not formatted: {}", path.display());
+    } else {
+        write(&path, formatted).expect("Unable to write to file");
+    }
+
+    true
 }
 
 #[cfg(test)]
@@ -75,22 +254,71 @@ mod tests {
 
     use super::*;
 
+    use format::{options::FormatOptions, render::PrettyPrintParameters};
+
     use test_each_file::test_each_file;
 
     test_each_file! { in "./data" => compare_parsed_to_original }
 
+    // Rewrites change the program, so their goldens come with the expected result
+    mod rewrites {
+        mod imports {
+            use super::super::*;
+
+            test_each_file! { for ["java", "expected"] in "./data/imports" => compare_rewritten_to_expected }
+        }
+
+        mod suppression {
+            use super::super::*;
+
+            test_each_file! { for ["java", "expected"] in "./data/suppression" => compare_rewritten_to_expected }
+        }
+    }
+
     fn compare_parsed_to_original(content: &str) {
+        assert_eq!(content, format_source(content, false));
+    }
+
+    fn compare_rewritten_to_expected([content, expected]: [&str; 2]) {
+        assert_eq!(expected, format_source(content, true));
+    }
+
+    fn format_source(content: &str, rewrites: bool) -> String {
         let mut parser = Parser::of(Language::Java);
 
-        let tree = parser.parse(&content).unwrap();
+        let encoding = Encoding::detect(content);
+        let normalized = Encoding::normalize(content);
+
+        let tree = parser.parse(&normalized).unwrap();
+
+        let rules = FormatRules::of(Language::Java);
+
+        let rewritten = if rewrites {
+            rewrite(&tree, &rules, &RewriteOptions::default())
+        } else {
+            None
+        };
+
+        let tree = match &rewritten {
+            Some(rewritten) => parser.parse(rewritten).unwrap(),
+            None => tree,
+        };
 
         print_as_tree(&tree, 0);
 
         // is this an issue for unicode characters outside ascii?
-        let formatted = print(&tree, &PrettyPrintParameters { indent_size: 4, max_line_length: 100 });
+        let formatted = print(
+            &tree,
+            &rules,
+            &FormatOptions::default(),
+            &PrettyPrintParameters {
+                indent_size: 4,
+                max_line_length: 100,
+            },
+        );
 
         // println!("{}", formatted);
 
-        assert_eq!(content, formatted);
+        encoding.restore(&formatted)
     }
 }
@@ -0,0 +1,139 @@
+use parser::tree::Tree;
+
+use crate::rewrite::Edit;
+
+/** Canonical modifier order, following the JLS */
+const MODIFIER_ORDER: &[&str] = &[
+    "public",
+    "protected",
+    "private",
+    "abstract",
+    "default",
+    "static",
+    "final",
+    "sealed",
+    "non-sealed",
+    "transient",
+    "volatile",
+    "synchronized",
+    "native",
+    "strictfp",
+];
+
+/**
+ * Sorts modifier keywords into the canonical order
+ *
+ * Annotations before the last keyword are moved ahead of the keywords, while annotations after all keywords are kept
+ * next to the type they likely apply to
+ */
+pub fn order_modifiers(program: &Tree) -> Vec<Edit> {
+    let mut edits = Vec::new();
+
+    visit(program, &mut edits);
+
+    edits
+}
+
+fn visit(node: &Tree, edits: &mut Vec<Edit>) {
+    if node.name() == "modifiers" {
+        edits.extend(order(node));
+        return;
+    }
+
+    for child in node.children() {
+        visit(child, edits);
+    }
+}
+
+fn order(modifiers: &Tree) -> Option<Edit> {
+    let children = modifiers.children();
+
+    // Comments have no clear place once reordered
+    if children
+        .iter()
+        .any(|child| child.name().ends_with("comment"))
+    {
+        return None;
+    }
+
+    let last_keyword = children.iter().rposition(|child| !is_annotation(child))?;
+
+    let (leading, trailing) = children.split_at(last_keyword + 1);
+
+    let mut keywords: Vec<&Tree> = leading
+        .iter()
+        .filter(|child| !is_annotation(child))
+        .collect();
+    keywords.sort_by_key(|keyword| {
+        MODIFIER_ORDER
+            .iter()
+            .position(|name| *name == keyword.name())
+            .unwrap_or(MODIFIER_ORDER.len())
+    });
+
+    let ordered: Vec<&Tree> = leading
+        .iter()
+        .filter(|child| is_annotation(child))
+        .chain(keywords)
+        .chain(trailing)
+        .collect();
+
+    if ordered
+        .iter()
+        .zip(children)
+        .all(|(ordered, original)| ordered.id() == original.id())
+    {
+        return None;
+    }
+
+    Some(Edit {
+        range: modifiers.range().start_byte..modifiers.range().end_byte,
+        replacement: ordered
+            .iter()
+            .map(|child| child.text())
+            .collect::<Vec<&str>>()
+            .join(" "),
+    })
+}
+
+pub(crate) fn is_annotation(node: &Tree) -> bool {
+    node.name() == "annotation" || node.name() == "marker_annotation"
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::{language::Language, parser::Parser};
+
+    use super::*;
+    use crate::rewrite::apply;
+
+    fn reorder(source: &str) -> String {
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+
+        apply(source, order_modifiers(&tree)).unwrap_or_else(|| source.to_owned())
+    }
+
+    #[test]
+    fn orders_keywords() {
+        assert_eq!(
+            reorder("class A { final static public int X = 1; }"),
+            "class A { public static final int X = 1; }"
+        );
+    }
+
+    #[test]
+    fn moves_annotations_before_keywords() {
+        assert_eq!(
+            reorder("class A { public @Override final @Nullable String a() { } }"),
+            "class A { @Override public final @Nullable String a() { } }"
+        );
+    }
+
+    #[test]
+    fn keeps_ordered_modifiers() {
+        let source = "@Deprecated public abstract class A { }";
+
+        assert_eq!(reorder(source), source);
+    }
+}
@@ -0,0 +1,161 @@
+use std::str::FromStr;
+
+/** Layout preferences which vary between projects, as opposed to the rules of the language being formatted */
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /** Chains of more calls than this always have a call per line, even if they would fit on one */
+    pub max_chain_calls: usize,
+    pub brace_style: BraceStyle,
+    /** Most blank lines kept where the source has several in a row */
+    pub max_blank_lines: usize,
+    /** Blank line between the opening brace of a class, interface, enum or annotation and its first member */
+    pub blank_line_after_class_header: BlankLinePolicy,
+    /** Blank line between the last member of a class, interface, enum or annotation and its closing brace */
+    pub blank_line_before_closing_brace: BlankLinePolicy,
+    /** Whether if, for, while, do and try statements are always followed by a blank line, rather than as written */
+    pub blank_line_after_control_statements: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            max_chain_calls: 5,
+            brace_style: BraceStyle::EndOfLine,
+            max_blank_lines: 1,
+            blank_line_after_class_header: BlankLinePolicy::Preserve,
+            blank_line_before_closing_brace: BlankLinePolicy::Preserve,
+            blank_line_after_control_statements: true,
+        }
+    }
+}
+
+/** Where the opening brace of a body goes */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BraceStyle {
+    /** On the line of the declaration or statement, as in K&R */
+    EndOfLine,
+    /** On its own line, as in Allman */
+    NextLine,
+}
+
+impl FromStr for BraceStyle {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "end_of_line" | "k&r" => Ok(BraceStyle::EndOfLine),
+            "next_line" | "allman" => Ok(BraceStyle::NextLine),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlankLinePolicy {
+    /** Kept only where the source has one */
+    Preserve,
+    Always,
+    Never,
+}
+
+impl FromStr for BlankLinePolicy {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "preserve" => Ok(BlankLinePolicy::Preserve),
+            "always" => Ok(BlankLinePolicy::Always),
+            "never" => Ok(BlankLinePolicy::Never),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::{language::Language, parser::Parser};
+
+    use super::*;
+    use crate::{print::print, render::PrettyPrintParameters, transform::FormatRules};
+
+    fn format(source: &str, options: &FormatOptions) -> String {
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+
+        print(
+            &tree,
+            &FormatRules::of(Language::Java),
+            options,
+            &PrettyPrintParameters {
+                indent_size: 4,
+                max_line_length: 100,
+            },
+        )
+    }
+
+    #[test]
+    fn places_braces_on_next_line() {
+        let options = FormatOptions {
+            brace_style: BraceStyle::NextLine,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            format(
+                "class A {\n    void a() {\n        try {\n            go();\n        } catch (E e) {\n        } finally {\n            run(() -> {\n                stop();\n            });\n        }\n    }\n}\n",
+                &options
+            ),
+            "class A\n{\n    void a()\n    {\n        try\n        {\n            go();\n        }\n        catch (E e)\n        { }\n        finally\n        {\n            run(() -> {\n                stop();\n            });\n        }\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn limits_blank_lines() {
+        let source = "class A {\n    int x;\n\n\n\n    int y;\n}\n";
+
+        assert_eq!(
+            format(source, &FormatOptions::default()),
+            "class A {\n    int x;\n\n    int y;\n}\n"
+        );
+        assert_eq!(
+            format(
+                source,
+                &FormatOptions {
+                    max_blank_lines: 2,
+                    ..FormatOptions::default()
+                }
+            ),
+            "class A {\n    int x;\n\n\n    int y;\n}\n"
+        );
+    }
+
+    #[test]
+    fn applies_class_body_blank_lines() {
+        let options = FormatOptions {
+            blank_line_after_class_header: BlankLinePolicy::Always,
+            blank_line_before_closing_brace: BlankLinePolicy::Never,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            format("class A {\n    int x;\n\n}\n", &options),
+            "class A {\n\n    int x;\n}\n"
+        );
+    }
+
+    #[test]
+    fn keeps_control_statements_as_written() {
+        let options = FormatOptions {
+            blank_line_after_control_statements: false,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            format(
+                "void a() {\n    if (x) {\n        go();\n    }\n    stop();\n}\n",
+                &options
+            ),
+            "void a() {\n    if (x) {\n        go();\n    }\n    stop();\n}\n"
+        );
+    }
+}
@@ -1,12 +1,27 @@
 use parser::tree::Tree;
 
 use crate::format_node::FormatNode;
-use crate::render::{prettyprint, PrettyPrintParameters, WrapParameters};
-use crate::transform::transform;
+use crate::options::FormatOptions;
+use crate::render::{PrettyPrintParameters, WrapParameters, prettyprint, unmark_kept_lines};
+use crate::transform::{FormatRules, transform};
 
-pub fn print(node: &Tree, arguments: &PrettyPrintParameters) -> String {
-    // print_as_tree(&transform(node), 0);
-    return prettyprint(&transform(node), arguments, WrapParameters::default()).result + "\n";
+pub fn print(
+    node: &Tree,
+    rules: &FormatRules,
+    options: &FormatOptions,
+    arguments: &PrettyPrintParameters,
+) -> String {
+    let directives = rules.directives(node);
+
+    // print_as_tree(&transform(node, rules, options, &directives), 0);
+    unmark_kept_lines(
+        prettyprint(
+            &transform(node, rules, options, &directives),
+            arguments,
+            WrapParameters::default(),
+        )
+        .result,
+    ) + "\n"
 }
 
 #[allow(dead_code)]
@@ -18,6 +33,9 @@ pub fn print_as_tree(node: &FormatNode, indent: usize) {
         FormatNode::Wrap(_, _) => "Wrap",
         FormatNode::Space => "Space",
         FormatNode::Newline => "Newline",
+        FormatNode::LineSuffix(_) => "LineSuffix",
+        FormatNode::Javadoc(_) => "Javadoc",
+        FormatNode::TextBlock(_) => "TextBlock",
     };
 
     println!("{}{}", " ".repeat(indent), name);
@@ -33,7 +51,12 @@ pub fn print_as_tree(node: &FormatNode, indent: usize) {
     };
 
     match node {
-        FormatNode::Content(content) => println!("{}{}", " ".repeat(indent + 4), content),
+        FormatNode::Content(content)
+        | FormatNode::LineSuffix(content)
+        | FormatNode::Javadoc(content)
+        | FormatNode::TextBlock(content) => {
+            println!("{}{}", " ".repeat(indent + 4), content)
+        }
         FormatNode::Group(format_nodes) => print_children(format_nodes),
         FormatNode::Indent(format_node) => print_as_tree(format_node, indent + 4),
         FormatNode::Wrap(format_node, _) => print_as_tree(format_node, indent + 4),
@@ -0,0 +1,293 @@
+use std::ops::{Range, RangeInclusive};
+
+use parser::tree::Tree;
+
+use crate::{
+    options::FormatOptions,
+    render::{PrettyPrintParameters, WrapParameters, indent, prettyprint, unmark_kept_lines},
+    transform::{Directives, FormatRules, transform},
+};
+
+/**
+ * Formats only the statements / members overlapping the byte range, leaving the rest of the source untouched
+ *
+ * The smallest enclosing nodes that sit directly within a block are formatted at the indentation of that block
+ */
+pub fn print_range(
+    node: &Tree,
+    rules: &FormatRules,
+    options: &FormatOptions,
+    arguments: &PrettyPrintParameters,
+    range: Range<usize>,
+) -> String {
+    print_ranges(node, rules, options, arguments, &[range])
+}
+
+/** Formats the statements / members overlapping any of the byte ranges, see print_range */
+pub fn print_ranges(
+    node: &Tree,
+    rules: &FormatRules,
+    options: &FormatOptions,
+    arguments: &PrettyPrintParameters,
+    ranges: &[Range<usize>],
+) -> String {
+    let source = node.source();
+    let directives = rules.directives(node);
+
+    let mut targets: Vec<(&Tree, usize)> = ranges
+        .iter()
+        .flat_map(|range| enclosing(rules, node, range, 0))
+        .collect();
+
+    // Ranges may share targets, or select a node within another selected node
+    targets.sort_by_key(|(target, _)| {
+        (
+            target.range().start_byte,
+            usize::MAX - target.range().end_byte,
+        )
+    });
+    targets.dedup_by(|(inner, _), (outer, _)| inner.range().end_byte <= outer.range().end_byte);
+
+    let mut result = source.to_owned();
+
+    // Splice from the end so earlier offsets stay valid
+    for (target, depth) in targets.into_iter().rev() {
+        let start = target.range().start_byte;
+        let end = target.range().end_byte;
+
+        let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+        let starts_line = source[line_start..start].trim().is_empty();
+
+        let formatted = format(target, rules, options, &directives, arguments, depth);
+
+        if starts_line {
+            result.replace_range(line_start..end, &formatted);
+        } else {
+            // Something else precedes the node on its line, so it keeps its position
+            result.replace_range(start..end, formatted.trim_start());
+        }
+    }
+
+    result
+}
+
+/** Byte range covering the given 1-based, inclusive line numbers */
+pub fn line_range(source: &str, lines: RangeInclusive<usize>) -> Range<usize> {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+
+    let start = line_starts
+        .get(lines.start().saturating_sub(1))
+        .copied()
+        .unwrap_or(source.len());
+    let end = line_starts
+        .get(*lines.end())
+        .map_or(source.len(), |next_line| next_line - 1);
+
+    start..end.max(start)
+}
+
+fn format(
+    node: &Tree,
+    rules: &FormatRules,
+    options: &FormatOptions,
+    directives: &Directives,
+    arguments: &PrettyPrintParameters,
+    depth: usize,
+) -> String {
+    // The node is indented afterwards, so it has less room before reaching the maximum line length
+    let indented_arguments = PrettyPrintParameters {
+        indent_size: arguments.indent_size,
+        max_line_length: arguments
+            .max_line_length
+            .saturating_sub(depth * arguments.indent_size),
+    };
+
+    let formatted = prettyprint(
+        &transform(node, rules, options, directives),
+        &indented_arguments,
+        WrapParameters::default(),
+    )
+    .result;
+
+    unmark_kept_lines(indent(formatted, depth * arguments.indent_size))
+}
+
+/** Nodes overlapping the range which are direct children of a block, along with the depth of that block */
+fn enclosing<'tree, 'source>(
+    rules: &FormatRules,
+    block: &'tree Tree<'source>,
+    range: &Range<usize>,
+    depth: usize,
+) -> Vec<(&'tree Tree<'source>, usize)> {
+    let members: Vec<&Tree> = block
+        .children()
+        .iter()
+        .filter(|child| child.name() != "{" && child.name() != "}")
+        .filter(|child| overlaps(child, range))
+        .collect();
+
+    // A single member containing the whole range may have a block of its own to narrow down into
+    if let [member] = members[..]
+        && contains(member, range)
+        && let Some(inner) = inner_block(rules, member, range)
+    {
+        let found = enclosing(rules, inner, range, depth + 1);
+
+        if !found.is_empty() {
+            return found;
+        }
+    }
+
+    members.into_iter().map(|member| (member, depth)).collect()
+}
+
+/** The outermost block within the node which contains the range between its braces */
+fn inner_block<'tree, 'source>(
+    rules: &FormatRules,
+    node: &'tree Tree<'source>,
+    range: &Range<usize>,
+) -> Option<&'tree Tree<'source>> {
+    node.children()
+        .iter()
+        .filter(|child| contains(child, range))
+        .find_map(|child| {
+            if !rules.is_block(child) {
+                return inner_block(rules, child, range);
+            }
+
+            // A range covering the braces belongs to the node the block is part of
+            let within =
+                child.range().start_byte < range.start && range.end < child.range().end_byte;
+
+            within.then_some(child)
+        })
+}
+
+fn overlaps(node: &Tree, range: &Range<usize>) -> bool {
+    let node_range = node.range();
+
+    if range.is_empty() {
+        node_range.start_byte <= range.start && range.start <= node_range.end_byte
+    } else {
+        node_range.start_byte < range.end && range.start < node_range.end_byte
+    }
+}
+
+fn contains(node: &Tree, range: &Range<usize>) -> bool {
+    node.range().start_byte <= range.start && range.end <= node.range().end_byte
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::{language::Language, parser::Parser};
+
+    use super::*;
+    use crate::print::print;
+
+    const ARGUMENTS: PrettyPrintParameters = PrettyPrintParameters {
+        indent_size: 4,
+        max_line_length: 100,
+    };
+
+    fn format_lines(source: &str, lines: RangeInclusive<usize>) -> String {
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+
+        print_range(
+            &tree,
+            &FormatRules::of(Language::Java),
+            &FormatOptions::default(),
+            &ARGUMENTS,
+            line_range(source, lines),
+        )
+    }
+
+    #[test]
+    fn formats_only_selected_statement() {
+        let source = "class A {\n    void a() {\n        int  x=1;\n        int  y=2;\n    }\n}\n";
+
+        assert_eq!(
+            format_lines(source, 3..=3),
+            "class A {\n    void a() {\n        int x = 1;\n        int  y=2;\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn formats_each_overlapping_member_at_its_depth() {
+        let source = "class A {\nint  x;\n  void  a() { }\n\n    int  z;\n}\n";
+
+        assert_eq!(
+            format_lines(source, 2..=3),
+            "class A {\n    int x;\n    void a() { }\n\n    int  z;\n}\n"
+        );
+    }
+
+    #[test]
+    fn formats_multiple_ranges_once() {
+        let source = "class A {\nint  x;\nint  y;\nint  z;\n}\n";
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+
+        let ranges = [
+            line_range(source, 2..=2),
+            line_range(source, 1..=2),
+            line_range(source, 4..=4),
+        ];
+
+        assert_eq!(
+            print_ranges(
+                &tree,
+                &FormatRules::of(Language::Java),
+                &FormatOptions::default(),
+                &ARGUMENTS,
+                &ranges
+            ),
+            "class A {\n    int x;\n    int y;\n    int z;\n}\n"
+        );
+    }
+
+    #[test]
+    fn formats_statement_owning_selected_braces() {
+        let source = "class A {\n    void a() {\n        if (b) {\nc();\n}\n    }\n}\n";
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+        let block = source.find("{\nc").unwrap()..source.rfind("}\n    }").unwrap() + 1;
+
+        assert_eq!(
+            print_range(
+                &tree,
+                &FormatRules::of(Language::Java),
+                &FormatOptions::default(),
+                &ARGUMENTS,
+                block
+            ),
+            "class A {\n    void a() {\n        if (b) {\n            c();\n        }\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn wraps_nested_statement_as_whole_file_would() {
+        let source = "class A {\n    void a() {\n        if (b) {\n            String myReallyReallyReallyLongName = myReallyReallyReallyReallyReallyReallyReallyLongStupidName;\n        }\n    }\n}\n";
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+        let rules = FormatRules::of(Language::Java);
+        let options = FormatOptions::default();
+
+        assert_eq!(
+            format_lines(source, 4..=4),
+            print(&tree, &rules, &options, &ARGUMENTS)
+        );
+    }
+
+    #[test]
+    fn formats_top_level_declaration() {
+        let source = "class  A {\nint  x;\n}\nclass  B { }\n";
+
+        assert_eq!(
+            format_lines(source, 1..=1),
+            "class A {\n    int x;\n}\nclass  B { }\n"
+        );
+    }
+}
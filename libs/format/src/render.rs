@@ -1,4 +1,7 @@
-use crate::format_node::{FormatNode, WrapArguments};
+use crate::{
+    format_node::{FormatNode, WrapArguments},
+    javadoc,
+};
 
 pub struct PrettyPrintResult {
     pub result: String,
@@ -26,14 +29,34 @@ pub struct WrapParameters {
 }
 
 pub struct PrettyPrintParameters {
-    pub(crate) indent_size: usize,
-    pub(crate) max_line_length: usize,
+    pub indent_size: usize,
+    pub max_line_length: usize,
 }
 
 // TODO we need to integrate indentations with this
 const MAX_LINE_LENGTH: usize = 100;
 
-pub fn prettyprint(formatted: &FormatNode, arguments: &PrettyPrintParameters, parent_wrap: WrapParameters) -> PrettyPrintResult {
+/** Starts a line which is kept as written rather than indented, removed once the output is fully indented */
+const KEPT_LINE: char = '\0';
+
+/** Removes the markers of lines kept as written, once no more indentation will be applied */
+pub(crate) fn unmark_kept_lines(content: String) -> String {
+    if !content.contains(KEPT_LINE) {
+        return content;
+    }
+
+    content
+        .split('\n')
+        .map(|line| line.strip_prefix(KEPT_LINE).unwrap_or(line))
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+pub fn prettyprint(
+    formatted: &FormatNode,
+    arguments: &PrettyPrintParameters,
+    parent_wrap: WrapParameters,
+) -> PrettyPrintResult {
     // println!("{:?}", formatted);
 
     let transformed = print(
@@ -76,7 +99,11 @@ pub fn prettyprint(formatted: &FormatNode, arguments: &PrettyPrintParameters, pa
 
         let new = print(formatted, arguments, params, parent_wrap);
 
-        if transformed.result.lines().any(|line| line.len() > arguments.max_line_length) {
+        if transformed
+            .result
+            .lines()
+            .any(|line| line.len() > arguments.max_line_length)
+        {
             // panic!("Unexpectedly long line! {:?} {:?}", formatted, transformed);
         }
 
@@ -154,9 +181,41 @@ fn print(
             // }
             // .into()
         }
-        FormatNode::Indent(element) => indent(prettyprint(element, arguments, wrap).result, arguments.indent_size).into(),
+        FormatNode::Indent(element) => {
+            // Indented content has less room before reaching the maximum line length
+            let indented_arguments = PrettyPrintParameters {
+                indent_size: arguments.indent_size,
+                max_line_length: arguments
+                    .max_line_length
+                    .saturating_sub(arguments.indent_size),
+            };
+
+            indent(
+                prettyprint(element, &indented_arguments, wrap).result,
+                arguments.indent_size,
+            )
+            .into()
+        }
         FormatNode::Newline => "\n".to_owned().into(),
         FormatNode::Space => " ".to_owned().into(),
+        FormatNode::Javadoc(content) => javadoc::format(content, arguments.max_line_length).into(),
+        FormatNode::TextBlock(content) => content
+            .split('\n')
+            .enumerate()
+            .map(|(index, line)| {
+                if index == 0 {
+                    line.to_owned()
+                } else {
+                    format!("{KEPT_LINE}{line}")
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+            .into(),
+        FormatNode::LineSuffix(content) => PrettyPrintResult {
+            result: content.to_string(),
+            is_wrapped: true,
+        },
         // FormatNode::WrapBoundary(element) => PrettyPrintResult {
         //     result: prettyprint(element, wrap_children).result,
         //     is_wrapped: false,
@@ -164,10 +223,17 @@ fn print(
     }
 }
 
-fn indent(content: String, indent_size: usize) -> String {
+pub(crate) fn indent(content: String, indent_size: usize) -> String {
     content
         .lines()
-        .map(|line| (" ".repeat(indent_size).to_owned()) + line)
+        // Blank lines are left empty rather than gaining trailing whitespace
+        .map(|line| {
+            if line.is_empty() || line.starts_with(KEPT_LINE) {
+                line.to_owned()
+            } else {
+                (" ".repeat(indent_size).to_owned()) + line
+            }
+        })
         .collect::<Vec<String>>()
         .join("\n")
 }
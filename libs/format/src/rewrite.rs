@@ -0,0 +1,97 @@
+use std::ops::Range;
+
+use parser::tree::Tree;
+
+use crate::{
+    imports::{ImportLayout, organize_imports},
+    modifiers::order_modifiers,
+    transform::FormatRules,
+};
+
+/** Replacement of a byte range of the source */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+#[derive(Default)]
+pub struct RewriteOptions {
+    pub import_layout: ImportLayout,
+    pub remove_unused_imports: bool,
+}
+
+/**
+ * Rewrites the program ahead of formatting, for changes which reorder or remove nodes rather than lay them out
+ *
+ * Code which formatting keeps as written is not rewritten either
+ *
+ * Returns the rewritten source, which must be parsed again, or None if nothing changed
+ */
+pub fn rewrite(program: &Tree, rules: &FormatRules, options: &RewriteOptions) -> Option<String> {
+    let directives = rules.directives(program);
+
+    let edits = organize_imports(
+        program,
+        &options.import_layout,
+        options.remove_unused_imports,
+    )
+    .into_iter()
+    .chain(order_modifiers(program))
+    .filter(|edit| !directives.is_verbatim(&edit.range));
+
+    apply(program.source(), edits)
+}
+
+/** Applies non-overlapping edits to the source, or None if there are none */
+pub fn apply(source: &str, edits: impl IntoIterator<Item = Edit>) -> Option<String> {
+    let mut edits: Vec<Edit> = edits.into_iter().collect();
+
+    if edits.is_empty() {
+        return None;
+    }
+
+    // Apply from the end so earlier offsets stay valid
+    edits.sort_by_key(|edit| std::cmp::Reverse(edit.range.start));
+
+    debug_assert!(
+        edits
+            .windows(2)
+            .all(|pair| pair[1].range.end <= pair[0].range.start),
+        "Overlapping edits {edits:?}"
+    );
+
+    let mut rewritten = source.to_owned();
+
+    for edit in edits {
+        rewritten.replace_range(edit.range, &edit.replacement);
+    }
+
+    Some(rewritten)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(range: Range<usize>, replacement: &str) -> Edit {
+        Edit {
+            range,
+            replacement: replacement.to_owned(),
+        }
+    }
+
+    #[test]
+    fn applies_edits_in_any_order() {
+        assert_eq!(
+            apply("int x;", [edit(4..5, "y"), edit(0..3, "long")]),
+            Some("long y;".to_owned())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Overlapping edits")]
+    fn rejects_overlapping_edits() {
+        apply("int x;", [edit(0..5, "long y"), edit(4..5, "z")]);
+    }
+}
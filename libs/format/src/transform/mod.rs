@@ -1,4 +1,9 @@
 mod transform;
+mod transform_chain;
+mod transform_comments;
+mod transform_queries;
 mod transform_rules;
 
-pub(crate) use transform::transform;
+pub use transform::transform;
+pub use transform_queries::Directives;
+pub use transform_rules::FormatRules;
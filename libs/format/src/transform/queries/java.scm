@@ -0,0 +1,64 @@
+; Formatting directives for Java
+;
+; Directives are attached to nodes by capture name and apply between the captured node and its siblings:
+;   @prepend_space / @append_space     separate from the previous / next sibling with a space
+;   @prepend_newline / @append_newline separate from the previous / next sibling with a newline
+;   @indent                            start on a new, indented line
+;   @wrap_point                        allow wrapping (with indent) before this node
+;   @leaf                              keep the node as written
+;   @declaration_modifiers             put annotations ahead of the modifier keywords on their own lines
+;   @formatter_off / @formatter_on     keep everything after / before the captured node as written
+;
+; Directives that would apply before a first child or after a last child have no effect.
+
+; Operators
+
+(binary_expression
+  operator: _ @wrap_point)
+
+(binary_expression
+  _ @prepend_space)
+
+(assignment_expression
+  _ @prepend_space)
+
+(ternary_expression
+  _ @prepend_space)
+
+(instanceof_expression
+  _ @prepend_space)
+
+(lambda_expression
+  _ @prepend_space)
+
+; Declarations
+
+(variable_declarator
+  value: _ @wrap_point)
+
+; Annotations of fields, variables and parameters stay inline
+
+[
+  (class_declaration (modifiers) @declaration_modifiers)
+  (interface_declaration (modifiers) @declaration_modifiers)
+  (enum_declaration (modifiers) @declaration_modifiers)
+  (record_declaration (modifiers) @declaration_modifiers)
+  (annotation_type_declaration (modifiers) @declaration_modifiers)
+  (method_declaration (modifiers) @declaration_modifiers)
+  (constructor_declaration (modifiers) @declaration_modifiers)
+]
+
+; Suppression
+;
+; Formatting is switched off between @formatter_off and @formatter_on, and @leaf nodes are kept as written
+
+([(line_comment) (block_comment)] @formatter_off
+  (#match? @formatter_off "@formatter:off"))
+
+([(line_comment) (block_comment)] @formatter_on
+  (#match? @formatter_on "@formatter:on"))
+
+(((line_comment) @_ignore
+  .
+  (_) @leaf)
+  (#match? @_ignore "lint-format-ignore"))
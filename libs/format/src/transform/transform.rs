@@ -1,8 +1,18 @@
 use crate::{
     format_node::{FormatNode, WrapArguments},
-    transform::transform_rules::{get, post_visit, pre_visit},
+    options::FormatOptions,
+    transform::{
+        transform_chain::transform_chain,
+        transform_comments::{
+            Comments, ends_line, is_comment, is_separated, next_significant, previous_significant,
+        },
+        transform_queries::Directives,
+        transform_rules::{FormatRules, post_visit, pre_visit},
+    },
 };
 
+use std::ops::Range;
+
 use parser::tree::Tree;
 
 #[derive(Debug, Default)]
@@ -10,21 +20,35 @@ pub(crate) struct FormatArguments {
     pub space: bool,
     pub newline: bool,
     pub double_newline: bool, // ignores newline - TODO better way to model this?
-    pub indent: bool,         // TODO do we need a separate one for wrap and indent?
+    /** Blank lines in the source before the next sibling, some of which are kept along with double_newline */
+    pub blank_lines: usize,
+    pub indent: bool, // TODO do we need a separate one for wrap and indent?
     pub wrap: bool,
     pub prevent_wrap_cascade: bool,
     pub child_wrap_prevents_wrap: bool,
 }
 
-pub fn transform<'source>(node: &Tree<'source>) -> FormatNode {
-    let parent_name = node.name();
-
+pub fn transform<'source>(
+    node: &Tree<'source>,
+    rules: &FormatRules,
+    options: &FormatOptions,
+    directives: &Directives,
+) -> FormatNode {
     #[derive(Debug)]
     struct FormatContainer {
         children: Vec<FormatNode>,
         wrapping: Option<WrapArguments>,
     }
 
+    // Indenting the lines of a text block would change its value, so they are kept as written
+    if node.name() == "string_literal" && node.text().starts_with("\"\"\"") {
+        return FormatNode::TextBlock(node.text().into());
+    }
+
+    if let Some(chain) = transform_chain(node, rules, options, directives) {
+        return chain;
+    }
+
     // TODO consider writing using TreeCursor
     if node.children().len() > 0 {
         // TODO prevent double wrap with function parameters
@@ -38,21 +62,59 @@ pub fn transform<'source>(node: &Tree<'source>) -> FormatNode {
 
         let mut between = FormatArguments::default();
 
+        let children = node.children();
+        let comments = Comments::of(children);
+
         // TODO create a local pop function
 
-        for index in 0..node.children().len() {
-            let child = &node.children()[index];
-            let child_name = child.name();
+        let mut index = 0;
+
+        while index < children.len() {
+            let child = &children[index];
+
+            // Index of the last sibling emitted along with this child, as they are all kept as written
+            let verbatim_end = directives.verbatim_run(children, index);
+            let last_index = verbatim_end.unwrap_or(index);
 
             // preprocess
-            if let Some(previous) = index
+            if let Some(previous_sibling) = index
                 .checked_sub(1)
-                .and_then(|prev_index| node.children().get(prev_index))
+                .and_then(|prev_index| children.get(prev_index))
             {
-                let previous_name = previous.name();
+                // Comments are looked past, so they don't change how the nodes around them are laid out
+                let previous = previous_significant(children, index).unwrap_or(previous_sibling);
+
+                if comments.trailing_owner(index).is_some() {
+                    between.space =
+                        child.name() == "line_comment" || is_separated(previous_sibling, child);
+                } else {
+                    // Should we always call this?
+                    pre_visit(&rules.config, options, node, &mut between, child, previous);
+
+                    directives.visit(&mut between, child, previous);
 
-                // Should we always call this?
-                pre_visit(parent_name, &mut between, child_name, previous_name);
+                    if is_comment(child) && !between.wrap && !rules.is_block(node) {
+                        between.newline = true;
+                        between.indent = true;
+                    }
+                }
+
+                if is_comment(previous_sibling) {
+                    if ends_line(previous_sibling, child) {
+                        if !between.wrap {
+                            between.newline = true;
+                            // Continues the parent on the next line, unless it is already laid out as lines
+                            // or the next node is a clause that lines up with the statement's start
+                            between.indent |= !rules.is_block(node)
+                                && !matches!(
+                                    child.name(),
+                                    "else" | "catch_clause" | "finally_clause" | "while"
+                                );
+                        }
+                    } else {
+                        between.space = is_separated(previous_sibling, child);
+                    }
+                }
             }
 
             if between.wrap {
@@ -84,10 +146,10 @@ pub fn transform<'source>(node: &Tree<'source>) -> FormatNode {
             // stack push
             if let Some(previous) = index
                 .checked_sub(1)
-                .and_then(|prev_index| node.children().get(prev_index))
+                .and_then(|prev_index| children.get(prev_index))
             {
-                if get().stack_pushers.contains(previous.name())
-                    && !get().stack_poppers.contains(child_name)
+                if rules.config.stack_pushers.contains(previous)
+                    && !rules.config.stack_poppers.contains(child)
                 {
                     stack_pushers_depth.push(stack.len());
 
@@ -99,7 +161,24 @@ pub fn transform<'source>(node: &Tree<'source>) -> FormatNode {
             }
 
             // process
-            let processed = transform(&child);
+            let processed = match verbatim_end {
+                Some(end) => verbatim(child, &children[end]),
+                // Anything following must be pushed onto the next line rather than commented out
+                None if child.name() == "line_comment"
+                    && comments.trailing_owner(index).is_some()
+                    && !rules.is_block(node) =>
+                {
+                    FormatNode::LineSuffix(child.text().into())
+                }
+                None if child.name() == "block_comment"
+                    && is_javadoc(child)
+                    && next_significant(children, index)
+                        .is_some_and(|next| rules.is_declaration(next)) =>
+                {
+                    FormatNode::Javadoc(child.text().into())
+                }
+                None => transform(child, rules, options, directives),
+            };
 
             match between {
                 FormatArguments { wrap: true, .. } => {}
@@ -107,8 +186,11 @@ pub fn transform<'source>(node: &Tree<'source>) -> FormatNode {
                     double_newline: true,
                     ..
                 } => {
-                    stack.last_mut().unwrap().children.push(FormatNode::Newline);
-                    stack.last_mut().unwrap().children.push(FormatNode::Newline);
+                    let blank_lines = between.blank_lines.min(options.max_blank_lines).max(1);
+
+                    for _ in 0..=blank_lines {
+                        stack.last_mut().unwrap().children.push(FormatNode::Newline);
+                    }
                 }
                 FormatArguments { indent: true, .. } => {
                     stack.last_mut().unwrap().children.push(FormatNode::Newline);
@@ -135,11 +217,13 @@ pub fn transform<'source>(node: &Tree<'source>) -> FormatNode {
 
             between = FormatArguments::default();
 
+            let child = &children[last_index];
+
             // stack pop
-            if let Some(next) = node.children().get(index + 1) {
+            if let Some(next) = children.get(last_index + 1) {
                 if stack_pushers_depth.len() == 0 {
                     // Warn
-                } else if get().stack_poppers.contains(next.name()) {
+                } else if rules.config.stack_poppers.contains(next) {
                     let expected_depth = stack_pushers_depth.pop().unwrap();
 
                     while stack.len() > expected_depth {
@@ -157,20 +241,43 @@ pub fn transform<'source>(node: &Tree<'source>) -> FormatNode {
             }
 
             // postprocess
-            if let Some(next) = node.children().get(index + 1) {
-                let next_name = next.name();
-
-                let has_multiple_newlines =
-                    (next.range().start_point.row - child.range().end_point.row) > 1;
-
-                post_visit(
-                    parent_name,
-                    &mut between,
-                    child_name,
-                    has_multiple_newlines,
-                    next_name,
-                );
+            if let Some(next_sibling) = children.get(last_index + 1) {
+                let blank_lines = (next_sibling.range().start_point.row
+                    - child.range().end_point.row)
+                    .saturating_sub(1);
+                let has_multiple_newlines = blank_lines > 0 && options.max_blank_lines > 0;
+
+                between.blank_lines = blank_lines;
+
+                if directives.is_verbatim_between(child, next_sibling) {
+                    // Only keep blank lines, since the next sibling still needs its indentation applied
+                    between.double_newline = has_multiple_newlines;
+                } else if comments.trailing_owner(last_index + 1).is_some() {
+                    // Laid out after the trailing comment instead
+                } else {
+                    let owner = match comments.trailing_owner(last_index) {
+                        Some(owner) => &children[owner],
+                        None => child,
+                    };
+                    let next = if is_comment(next_sibling) {
+                        next_significant(children, last_index).unwrap_or(next_sibling)
+                    } else {
+                        next_sibling
+                    };
+
+                    post_visit(
+                        &rules.config,
+                        options,
+                        node,
+                        &mut between,
+                        owner,
+                        has_multiple_newlines,
+                        next,
+                    );
+                }
             }
+
+            index = last_index + 1;
         }
 
         // TODO need to put the last item in a wrap as well?
@@ -194,25 +301,61 @@ pub fn transform<'source>(node: &Tree<'source>) -> FormatNode {
                 .children,
         )
     } else {
-        let original_content = node.text();
         let content = if node.name() == "block_comment" {
-            original_content
-                .lines()
-                .map(|line| line.trim_start())
-                .enumerate()
-                .map(|(index, line)| {
-                    if index == 0 {
-                        line.to_owned()
-                    } else {
-                        " ".to_owned() + line
-                    }
-                })
-                .collect::<Vec<String>>()
-                .join("\n")
+            dedent(
+                node.source(),
+                node.range().start_byte..node.range().end_byte,
+            )
         } else {
-            original_content.into()
+            node.text().into()
         };
 
         FormatNode::Content(content.into())
     }
 }
+
+/** The source from the start of the first node to the end of the last, as written */
+fn verbatim(first: &Tree, last: &Tree) -> FormatNode {
+    FormatNode::Content(
+        dedent(
+            first.source(),
+            first.range().start_byte..last.range().end_byte,
+        )
+        .into(),
+    )
+}
+
+/**
+ * The source in the range, with following lines keeping only their indentation relative to the first line
+ *
+ * Indentation is re-applied when rendering
+ */
+fn dedent(source: &str, range: Range<usize>) -> String {
+    let line_start = source[..range.start]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+    let line = &source[line_start..range.start];
+    let base_indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+
+    source[range]
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 {
+                return line;
+            }
+
+            let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+
+            &line[indent.min(base_indent)..]
+        })
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+/** Whether the comment is written as Javadoc, rather than a banner of asterisks or an empty comment */
+fn is_javadoc(comment: &Tree) -> bool {
+    let text = comment.text();
+
+    text.starts_with("/**") && !text.starts_with("/***") && text != "/**/"
+}
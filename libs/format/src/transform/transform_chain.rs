@@ -0,0 +1,103 @@
+use parser::tree::Tree;
+
+use crate::{
+    format_node::{FormatNode, WrapArguments},
+    options::FormatOptions,
+    transform::{
+        transform::transform, transform_comments::is_comment, transform_queries::Directives,
+        transform_rules::FormatRules,
+    },
+};
+
+/**
+ * Lays out a chain of calls, such as a stream or builder, as a single group
+ *
+ * The receiver is kept with the first call, then each following call is broken onto its own line when the chain does
+ * not fit or has more calls than the options allow
+ *
+ * Returns None for nodes which are not chains of more than one call, which are laid out as any other node
+ */
+pub(super) fn transform_chain(
+    node: &Tree,
+    rules: &FormatRules,
+    options: &FormatOptions,
+    directives: &Directives,
+) -> Option<FormatNode> {
+    if !is_link(node) {
+        return None;
+    }
+
+    let mut links: Vec<&[Tree]> = vec![];
+    let mut receiver = node;
+
+    while is_link(receiver) {
+        let children = receiver.children();
+
+        links.push(&children[1..]);
+        receiver = &children[0];
+    }
+
+    links.reverse();
+
+    // Fields accessed on the receiver stay with it, up to and including the first call
+    let head_length = links.iter().position(|link| is_call(link))? + 1;
+
+    if head_length == links.len() {
+        return None;
+    }
+
+    let calls = links.iter().filter(|link| is_call(link)).count();
+
+    let transform_link = |link: &[Tree]| {
+        FormatNode::Group(
+            link.iter()
+                .map(|child| transform(child, rules, options, directives))
+                .collect(),
+        )
+    };
+
+    let wrap = |content: FormatNode| {
+        FormatNode::Wrap(
+            content.into(),
+            WrapArguments {
+                child_wrap_prevents_wrap: true,
+                wrap_with_indent: true,
+                or_space: false,
+            },
+        )
+    };
+
+    // The head only breaks when it is too long on its own, like a single call
+    let head = std::iter::once(transform(receiver, rules, options, directives))
+        .chain(
+            links[..head_length]
+                .iter()
+                .map(|link| wrap(transform_link(link))),
+        )
+        .collect();
+
+    let mut chain = vec![FormatNode::Group(head)];
+
+    for link in &links[head_length..] {
+        if calls > options.max_chain_calls {
+            chain.push(FormatNode::Newline);
+            chain.push(FormatNode::Indent(transform_link(link).into()));
+        } else {
+            chain.push(wrap(transform_link(link)));
+        }
+    }
+
+    Some(FormatNode::Group(chain))
+}
+
+/** A call or field access on an object, without comments that would need laying out around it */
+fn is_link(node: &Tree) -> bool {
+    matches!(node.name(), "method_invocation" | "field_access")
+        && node.children().get(1).is_some_and(|dot| dot.name() == ".")
+        && !node.children().iter().any(is_comment)
+}
+
+fn is_call(link: &[Tree]) -> bool {
+    link.last()
+        .is_some_and(|child| child.name() == "argument_list")
+}
@@ -0,0 +1,74 @@
+use parser::tree::Tree;
+
+/** Comments among the children of a node which trail a significant sibling, so formatting rules can look past them */
+pub(super) struct Comments {
+    owners: Vec<Option<usize>>,
+}
+
+impl Comments {
+    pub(super) fn of(children: &[Tree]) -> Comments {
+        let mut owners = vec![None; children.len()];
+
+        if !children.iter().any(is_comment) {
+            return Comments { owners };
+        }
+
+        let mut owner = None;
+
+        for (index, child) in children.iter().enumerate() {
+            if !is_comment(child) {
+                owner = Some(index);
+                continue;
+            }
+
+            let same_line_as_previous = index > 0
+                && children[index - 1].range().end_point.row == child.range().start_point.row;
+
+            if same_line_as_previous {
+                owners[index] = owner;
+            }
+        }
+
+        Comments { owners }
+    }
+
+    /** Index of the sibling on whose last line the comment at the index sits, if it is a trailing comment */
+    pub(super) fn trailing_owner(&self, index: usize) -> Option<usize> {
+        self.owners.get(index).copied().flatten()
+    }
+}
+
+/** The closest sibling before the index that is not a comment */
+pub(super) fn previous_significant<'tree, 'source>(
+    children: &'tree [Tree<'source>],
+    index: usize,
+) -> Option<&'tree Tree<'source>> {
+    children[..index]
+        .iter()
+        .rev()
+        .find(|child| !is_comment(child))
+}
+
+/** The closest sibling after the index that is not a comment */
+pub(super) fn next_significant<'tree, 'source>(
+    children: &'tree [Tree<'source>],
+    index: usize,
+) -> Option<&'tree Tree<'source>> {
+    children[index + 1..]
+        .iter()
+        .find(|child| !is_comment(child))
+}
+
+pub(super) fn is_comment(node: &Tree) -> bool {
+    node.name() == "line_comment" || node.name() == "block_comment"
+}
+
+/** Whether anything following the comment must go on the next line */
+pub(super) fn ends_line(comment: &Tree, next: &Tree) -> bool {
+    next.range().start_point.row > comment.range().end_point.row
+}
+
+/** Whether the source separates the nodes with whitespace, which is kept around inline comments */
+pub(super) fn is_separated(previous: &Tree, next: &Tree) -> bool {
+    previous.range().end_byte < next.range().start_byte
+}
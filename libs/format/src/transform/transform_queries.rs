@@ -0,0 +1,214 @@
+use std::{collections::HashMap, ops::Range};
+
+use parser::{language::Language, query::Query, tree::Tree};
+
+use super::transform::FormatArguments;
+use crate::modifiers::is_annotation;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Directive {
+    PrependSpace,
+    AppendSpace,
+    PrependNewline,
+    AppendNewline,
+    Indent,
+    WrapPoint,
+}
+
+impl Directive {
+    fn from_capture(name: &str) -> Option<Directive> {
+        match name {
+            "prepend_space" => Some(Directive::PrependSpace),
+            "append_space" => Some(Directive::AppendSpace),
+            "prepend_newline" => Some(Directive::PrependNewline),
+            "append_newline" => Some(Directive::AppendNewline),
+            "indent" => Some(Directive::Indent),
+            "wrap_point" => Some(Directive::WrapPoint),
+            _ => None,
+        }
+    }
+}
+
+pub(super) struct FormatQuery {
+    query: Query,
+}
+
+impl FormatQuery {
+    pub(super) fn of(language: Language) -> FormatQuery {
+        let source = match language {
+            Language::Java => include_str!("queries/java.scm"),
+        };
+
+        FormatQuery {
+            query: Query::new(language, source).expect("Invalid formatting query"),
+        }
+    }
+
+    pub(super) fn directives(&self, tree: &Tree) -> Directives {
+        let mut directives = Directives::default();
+
+        // Positions where formatting is switched off (false) or back on (true)
+        let mut toggles: Vec<(usize, bool)> = Vec::new();
+
+        for found in self.query.matches(tree) {
+            for capture in found.captures {
+                let range = capture.node.range();
+
+                match capture.name.as_str() {
+                    "leaf" => directives.verbatim.push(range.start_byte..range.end_byte),
+                    "declaration_modifiers" => {
+                        for node in own_line_annotations(capture.node) {
+                            directives
+                                .by_node
+                                .entry(node.id())
+                                .or_default()
+                                .push(Directive::AppendNewline);
+                        }
+                    }
+                    "formatter_off" => toggles.push((range.end_byte, false)),
+                    "formatter_on" => toggles.push((range.start_byte, true)),
+                    name => {
+                        if let Some(directive) = Directive::from_capture(name) {
+                            directives
+                                .by_node
+                                .entry(capture.node.id())
+                                .or_default()
+                                .push(directive);
+                        }
+                    }
+                }
+            }
+        }
+
+        toggles.sort();
+
+        let mut off_since = None;
+
+        for (position, enabled) in toggles {
+            match (off_since, enabled) {
+                (None, false) => off_since = Some(position),
+                (Some(start), true) => {
+                    directives.verbatim.push(start..position);
+                    off_since = None;
+                }
+                _ => {} // repeated markers have no effect
+            }
+        }
+
+        // A region left open runs until the end of the file
+        if let Some(start) = off_since {
+            directives.verbatim.push(start..tree.source().len());
+        }
+
+        directives
+    }
+}
+
+/** Directives from the formatting query, keyed by node id, and the regions of source to keep as written */
+#[derive(Default)]
+pub struct Directives {
+    by_node: HashMap<usize, Vec<Directive>>,
+    verbatim: Vec<Range<usize>>,
+}
+
+impl Directives {
+    fn get(&self, node: &Tree) -> &[Directive] {
+        self.by_node
+            .get(&node.id())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /**
+     * If the child at the index is within a region to keep as written, the index of the last of the following
+     * siblings also within that region
+     *
+     * Braces are always formatted, so that blocks stay balanced when a region starts or ends inside of them
+     */
+    pub(super) fn verbatim_run(&self, children: &[Tree], index: usize) -> Option<usize> {
+        let is_brace = |node: &Tree| node.name() == "{" || node.name() == "}";
+
+        let child = &children[index];
+
+        if is_brace(child) {
+            return None;
+        }
+
+        let region = self.verbatim.iter().find(|region| within(child, region))?;
+
+        let mut end = index;
+
+        while let Some(next) = children.get(end + 1)
+            && !is_brace(next)
+            && within(next, region)
+        {
+            end += 1;
+        }
+
+        Some(end)
+    }
+
+    /** Whether any of the range is within a region to keep as written */
+    pub fn is_verbatim(&self, range: &Range<usize>) -> bool {
+        self.verbatim
+            .iter()
+            .any(|region| region.start < range.end && range.start < region.end)
+    }
+
+    /** Whether the whitespace between the nodes is within a region to keep as written */
+    pub(super) fn is_verbatim_between(&self, child: &Tree, next: &Tree) -> bool {
+        self.verbatim.iter().any(|region| {
+            region.start <= child.range().end_byte && next.range().start_byte <= region.end
+        })
+    }
+
+    pub(super) fn visit(&self, between: &mut FormatArguments, child: &Tree, previous: &Tree) {
+        for directive in self.get(previous) {
+            match directive {
+                Directive::AppendSpace => between.space = true,
+                Directive::AppendNewline => between.newline = true,
+                _ => {}
+            }
+        }
+
+        for directive in self.get(child) {
+            match directive {
+                Directive::PrependSpace => between.space = true,
+                Directive::PrependNewline => between.newline = true,
+                Directive::Indent => {
+                    between.newline = true;
+                    between.indent = true;
+                }
+                Directive::WrapPoint => {
+                    between.wrap = true;
+                    between.indent = true;
+                    between.child_wrap_prevents_wrap = true;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/**
+ * Annotations ahead of the last modifier keyword, or all of them along with the modifiers if there are no keywords
+ *
+ * Annotations after the keywords likely apply to the type, so stay inline
+ */
+fn own_line_annotations<'tree, 'source>(
+    modifiers: &'tree Tree<'source>,
+) -> Vec<&'tree Tree<'source>> {
+    let children = modifiers.children();
+
+    match children.iter().rposition(|child| !is_annotation(child)) {
+        Some(last_keyword) => children[..last_keyword]
+            .iter()
+            .filter(|child| is_annotation(child))
+            .collect(),
+        None => children.iter().chain([modifiers]).collect(),
+    }
+}
+
+fn within(node: &Tree, region: &Range<usize>) -> bool {
+    region.start <= node.range().start_byte && node.range().end_byte <= region.end
+}
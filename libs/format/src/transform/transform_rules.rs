@@ -1,275 +1,444 @@
-use std::collections::HashSet;
+use parser::{language::Language, tree::Tree};
 
-use super::transform::FormatArguments;
+use crate::options::{BlankLinePolicy, BraceStyle, FormatOptions};
 
-pub(super) fn pre_visit(parent: &str, between: &mut FormatArguments, child: &str, previous: &str) {
+use super::{
+    transform::FormatArguments,
+    transform_queries::{Directives, FormatQuery},
+};
+
+pub(super) fn pre_visit(
+    config: &FormatConfig,
+    options: &FormatOptions,
+    parent: &Tree,
+    between: &mut FormatArguments,
+    child: &Tree,
+    previous: &Tree,
+) {
     // if newline_before.contains(child_name) {
     //     stack.last_mut().unwrap().children.push(FormatNode::Newline);
     // }
 
     // TODO should we just have a list of syntax elements / nonleaf nodes
-    if parent == "binary_expression" {
-        if get().multi_operators.contains(child) {
-            between.wrap = true;
-            between.indent = true;
-            between.child_wrap_prevents_wrap = true;
-        }
-    }
-
-    if parent == "variable_declarator" {
-        if previous == "=" {
-            between.wrap = true;
-            between.indent = true;
-            between.child_wrap_prevents_wrap = true;
-        }
-    }
+    // (operators and assignments are handled by the formatting query in queries/)
 
-    if get().add_wrap_before.contains(child) && parent != "scoped_identifier" {
+    if config.add_wrap_before.contains(child) && parent.name() != "scoped_identifier" {
         between.wrap = true;
         between.indent = true;
         between.child_wrap_prevents_wrap = true;
     }
 
-    if get().wrap_list.contains(parent) {
-        if previous == "(" || previous == "{" {
+    if config.wrap_list.contains(parent) {
+        if previous.name() == "(" || previous.name() == "{" {
             between.wrap = true;
             between.indent = true; // TODO do we need to separate both?
         // between.child_wrap_prevents_wrap = false;
-        } else if previous == "," {
+        } else if previous.name() == "," {
             between.wrap = true;
             // between.indent = true; // TODO do we need to separate both?
             between.space = true;
         }
 
-        if child == ")" || child == "}" {
+        if child.name() == ")" || child.name() == "}" {
             between.wrap = true;
         }
     }
 
-    if parent == "program" || get().block_elements.contains(parent) {
+    if parent.name() == "program" || config.block_elements.contains(parent) {
         between.prevent_wrap_cascade = true;
 
-        if child == "}" {
-            if previous == "{" {
+        if child.name() == "}" {
+            if previous.name() == "{" {
                 between.space = true;
             } else {
                 between.newline = true;
             }
         } else {
             between.newline = true;
-            if parent != "program" {
+            if parent.name() != "program" {
                 between.indent = true;
             }
         }
-    } else if get().unconditional_space.contains(parent) {
-        between.space = true;
     }
-    if get().spaced_nodes.contains(parent) {
-        if !get().no_space_after.contains(previous) && !get().no_space_before.contains(child) {
+    // Statements of a case are indented beneath its labels, other than a lone block which follows the label
+    if parent.name() == "switch_block_statement_group" && child.name() != ":" {
+        if child.name() == "switch_label" {
+            between.newline = true;
+        } else if previous.name() == ":"
+            && child.name() == "block"
+            && parent.children().last().map(|last| last.id()) == Some(child.id())
+        {
             between.space = true;
+        } else {
+            between.newline = true;
+            between.indent = true;
+        }
+    }
+
+    // Commas and the semicolon ending the constants stay on the line of the last constant, with members indented
+    // by the declarations themselves
+    if parent.name() == "enum_body"
+        && (child.name() == "," || child.name() == "enum_body_declarations")
+    {
+        between.newline = false;
+        between.indent = false;
+        between.space = previous.name() == "{";
+    }
+
+    if parent.name() == "enum_body_declarations" {
+        between.newline = true;
+        between.indent = true;
+    }
+
+    if config.spaced_nodes.contains(parent) {
+        // Type parameters of a generic class and the parentheses of an annotation element follow its name
+        let follows_name = (child.name() == "type_parameters" || child.name() == "(")
+            && previous.name() == "identifier";
+
+        if !config.no_space_after.contains(previous)
+            && !config.no_space_before.contains(child)
+            && !follows_name
+        {
+            between.space = true;
+        }
+    }
+
+    if options.brace_style == BraceStyle::NextLine && !config.inline_bodies.contains(parent) {
+        // Bodies start on their own line, and so do the clauses following them
+        let opens_body =
+            config.block_elements.contains(child) && !config.block_elements.contains(parent);
+        let follows_body = (config.block_elements.contains(previous)
+            || previous
+                .children()
+                .last()
+                .is_some_and(|last| config.block_elements.contains(last)))
+            && matches!(
+                child.name(),
+                "else" | "catch_clause" | "finally_clause" | "while"
+            );
+
+        if opens_body || follows_body {
+            between.newline = true;
         }
     }
 }
 
 pub(super) fn post_visit(
-    parent: &str,
+    config: &FormatConfig,
+    options: &FormatOptions,
+    parent: &Tree,
     between: &mut FormatArguments,
-    child: &str,
+    child: &Tree,
     has_multiple_newlines: bool,
-    next: &str,
+    next: &Tree,
 ) {
-    if get().newline_after.contains(child) {
+    if config.newline_after.contains(child)
+        && options.blank_line_after_control_statements
+        && next.name() != "}"
+    {
         between.double_newline = true;
     }
 
-    if get().conditional_newline_after.contains(child) && next != "}" {
+    if config.conditional_newline_after.contains(child) && next.name() != "}" {
         between.double_newline = true;
     }
 
-    if child == "import_declaration" && next != "import_declaration" {
+    if child.name() == "import_declaration" && next.name() != "import_declaration" {
         between.double_newline = true;
     }
 
-    if parent.ends_with("_body") {
-        if child == "method_declaration" || child == "constructor_declaration" {
-            if next != "}" {
-                between.double_newline = true;
-            }
+    // Separates groups of imports
+    if child.name() == "import_declaration" && has_multiple_newlines {
+        between.double_newline = true;
+    }
+
+    if parent.name().ends_with("_body") || parent.name() == "enum_body_declarations" {
+        if (child.name() == "method_declaration" || child.name() == "constructor_declaration")
+            && next.name() != "}"
+        {
+            between.double_newline = true;
         }
 
-        if child == "field_declaration" && next != "field_declaration" && next != "}" {
+        if child.name() == "field_declaration"
+            && next.name() != "field_declaration"
+            && next.name() != "}"
+        {
             between.double_newline = true;
         }
     }
 
-    if get().block_elements.contains(parent) {
+    if parent.name() == "program"
+        || config.block_elements.contains(parent)
+        || parent.name() == "enum_body_declarations"
+    {
+        if child.name() == ";" && parent.name() == "enum_body_declarations" {
+            between.double_newline = true;
+        }
+
         if has_multiple_newlines {
             between.double_newline = true;
         }
     }
+
+    if config.type_bodies.contains(parent) && child.name() == "{" && next.name() != "}" {
+        apply(options.blank_line_after_class_header, between);
+    }
+
+    if config.type_bodies.contains(parent) && child.name() != "{" && next.name() == "}" {
+        apply(options.blank_line_before_closing_brace, between);
+    }
+}
+
+fn apply(policy: BlankLinePolicy, between: &mut FormatArguments) {
+    match policy {
+        BlankLinePolicy::Preserve => {}
+        BlankLinePolicy::Always => between.double_newline = true,
+        BlankLinePolicy::Never => between.double_newline = false,
+    }
 }
 
 // TODO to be replaced by DSL
 pub(super) struct FormatConfig {
-    block_elements: HashSet<&'static str>,
-    unconditional_space: HashSet<&'static str>,
-    spaced_nodes: HashSet<&'static str>,
-    newline_after: HashSet<&'static str>,
-    conditional_newline_after: HashSet<&'static str>,
-    no_space_before: HashSet<&'static str>,
-    wrap_list: HashSet<&'static str>,
-    no_space_after: HashSet<&'static str>,
-    add_wrap_before: HashSet<&'static str>,
-    multi_operators: HashSet<&'static str>,
-    pub stack_pushers: HashSet<&'static str>,
-    pub stack_poppers: HashSet<&'static str>,
+    block_elements: KindSet,
+    type_bodies: KindSet,
+    inline_bodies: KindSet,
+    spaced_nodes: KindSet,
+    newline_after: KindSet,
+    conditional_newline_after: KindSet,
+    no_space_before: KindSet,
+    wrap_list: KindSet,
+    no_space_after: KindSet,
+    add_wrap_before: KindSet,
+    declarations: KindSet,
+    pub stack_pushers: KindSet,
+    pub stack_poppers: KindSet,
+}
+
+impl FormatConfig {
+    fn of(language: Language) -> FormatConfig {
+        let set = |names: &[&str]| KindSet::of(language, names);
+
+        FormatConfig {
+            block_elements: set(&[
+                "class_body",
+                "enum_body",
+                "interface_body",
+                "block",
+                "constructor_body",
+                "switch_block",
+                "module_body",
+                "annotation_type_body",
+            ]), // children are indented unless there is no non-bracket element
+
+            type_bodies: set(&[
+                "class_body",
+                "interface_body",
+                "enum_body",
+                "annotation_type_body",
+            ]),
+
+            // bodies which stay on the line of their expression whatever the brace style
+            inline_bodies: set(&[
+                "lambda_expression",
+                "object_creation_expression",
+                "enum_constant",
+                "switch_rule",
+            ]),
+
+            spaced_nodes: set(&[
+                "class_declaration",
+                "enum_declaration",
+                "record_declaration",
+                "constructor_declaration",
+                "static_initializer",
+                "method_declaration",
+                "formal_parameter",
+                "return_statement",
+                "throw_statement",
+                "object_creation_expression",
+                "field_declaration",
+                "constant_declaration",
+                "local_variable_declaration",
+                "variable_declarator",
+                "object_creation_expression",
+                "package_declaration",
+                "import_declaration",
+                "interface_declaration",
+                "modifiers",
+                "throws",
+                "if_statement",
+                "try_statement",
+                "for_statement",
+                "do_statement",
+                "while_statement",
+                "finally_clause",
+                "catch_formal_parameter",
+                "resource",
+                "resource_specification",
+                "element_value_pair",
+                "try_with_resources_statement",
+                "type_parameters",
+                "type_arguments",
+                // "argument_list", // specifically handled with wrap
+                "inferred_parameters",
+                "formal_parameters",
+                "annotation_argument_list",
+                "element_value_array_initializer",
+                "catch_clause",
+                "enhanced_for_statement",
+                "switch_expression",
+                "switch_rule",
+                "switch_label",
+                "guard",
+                "type_pattern",
+                "yield_statement",
+                "compact_constructor_declaration",
+                "superclass",
+                "permits",
+                "type_list",
+                "type_parameter",
+                "type_bound",
+                "wildcard",
+                "record_pattern_body",
+                "record_pattern_component",
+                "enum_constant",
+                "annotation_type_declaration",
+                "annotation_type_element_declaration",
+                "module_declaration",
+                "requires_module_directive",
+                "exports_module_directive",
+                "opens_module_directive",
+                "uses_module_directive",
+                "provides_module_directive",
+                "super_interfaces", // TODO test
+                "implements",       // TODO test
+            ]), // items to add spaces between
+
+            // let newline_before = set(&[
+            // ]);
+            newline_after: set(&[
+                "if_statement",
+                "try_statement",
+                "for_statement",
+                "do_statement",
+                "while_statement",
+            ]),
+
+            conditional_newline_after: set(&[
+                "package_declaration",
+                "class_declaration",
+                "enum_declaration",
+                "record_declaration",
+                "interface_declaration",
+                "annotation_type_declaration",
+            ]),
+
+            // TODO implements
+            no_space_before: set(&[
+                ">",
+                ")",
+                ".",
+                ",",
+                ";",
+                "argument_list",
+                "formal_parameters",
+                "catch",
+            ]),
+
+            wrap_list: set(&[
+                "argument_list",
+                "parenthesized_expression",
+                "array_initializer",
+                "formal_parameters",
+            ]),
+
+            // TODO generic method call
+            no_space_after: set(&[
+                "(", "<", // unless as binary_operator
+            ]),
+
+            add_wrap_before: set(&["."]),
+
+            // declarations documented by a preceding Javadoc comment
+            declarations: set(&[
+                "package_declaration",
+                "module_declaration",
+                "class_declaration",
+                "interface_declaration",
+                "enum_declaration",
+                "record_declaration",
+                "annotation_type_declaration",
+                "constructor_declaration",
+                "compact_constructor_declaration",
+                "method_declaration",
+                "field_declaration",
+                "constant_declaration",
+                "enum_constant",
+                "annotation_type_element_declaration",
+            ]),
+
+            stack_pushers: set(&["(", "{"]),
+
+            stack_poppers: set(&[")", "}"]),
+        }
+    }
+}
+
+/** Set of node kinds, indexed by kind id to avoid hashing names */
+pub(super) struct KindSet {
+    kinds: Vec<bool>,
 }
 
-pub(super) fn get() -> FormatConfig {
-    FormatConfig {
-        block_elements: HashSet::from([
-            "class_body",
-            "enum_body",
-            "interface_body",
-            "block",
-            "constructor_body",
-        ]), // children are indented unless there is no non-bracket element
-
-        unconditional_space: HashSet::from([
-            "assignment_expression",
-            "binary_expression",
-            "ternary_expression",
-            "instanceof_expression",
-            "lambda_expression",
-        ]),
-
-        spaced_nodes: HashSet::from([
-            "class_declaration",
-            "enum_declaration",
-            "record_declaration",
-            "constructor_declaration",
-            "static_initializer",
-            "method_declaration",
-            "formal_parameter",
-            "return_statement",
-            "throw_statement",
-            "object_creation_expression",
-            "field_declaration",
-            "local_variable_declaration",
-            "variable_declarator",
-            "object_creation_expression",
-            "package_declaration",
-            "import_declaration",
-            "interface_declaration",
-            "modifiers",
-            "throws",
-            "if_statement",
-            "try_statement",
-            "for_statement",
-            "do_statement",
-            "while_statement",
-            "finally_clause",
-            "catch_formal_parameter",
-            "resource",
-            "resource_specification",
-            "element_value_pair",
-            "try_with_resources_statement",
-            "type_parameters",
-            "type_arguments",
-            // "argument_list", // specifically handled with wrap
-            "inferred_parameters",
-            "formal_parameters",
-            "annotation_argument_list",
-            "element_value_array_initializer",
-            "catch_clause",
-            "enhanced_for_statement",
-            // TODO superclasses
-            "super_interfaces", // TODO test
-            "implements",       // TODO test
-        ]), // items to add spaces between
-
-        // let newline_before = HashSet::from([
-        // ]);
-        newline_after: HashSet::from([
-            "if_statement",
-            "try_statement",
-            "for_statement",
-            "do_statement",
-            "while_statement",
-            "package_declaration",
-        ]),
-
-        conditional_newline_after: HashSet::from([
-            "class_declaration",
-            "enum_declaration",
-            "record_declaration",
-        ]),
-
-        // TODO implements
-        no_space_before: HashSet::from([
-            ">",
-            ")",
-            ".",
-            ",",
-            ";",
-            "argument_list",
-            "formal_parameters",
-            "catch",
-        ]),
-
-        wrap_list: HashSet::from([
-            "argument_list",
-            "parenthesized_expression",
-            "array_initializer",
-            "formal_parameters",
-        ]),
-
-        // TODO generic method call
-        no_space_after: HashSet::from([
-            "(", "<", // unless as binary_operator
-        ]),
-
-        add_wrap_before: HashSet::from(["."]),
-
-        stack_pushers: HashSet::from(["(", "{"]),
-
-        stack_poppers: HashSet::from([")", "}"]),
-
-        multi_operators: HashSet::from([
-            "?",
-            ":",
-            "*",
-            "/",
-            "%",
-            "+",
-            "-",
-            "<<",
-            ">>",
-            ">>>",
-            "<",
-            ">",
-            "<=",
-            ">=",
-            "instanceof",
-            "==",
-            "!=",
-            "&",
-            "^",
-            "|",
-            "&&",
-            "||",
-            "=",
-            "+=",
-            "-=",
-            "*=",
-            "/=",
-            "%=",
-            "&=",
-            "^=",
-            "|=",
-            "<<=",
-            ">>=",
-            ">>>=",
-        ]),
+impl KindSet {
+    fn of(language: Language, names: &[&str]) -> KindSet {
+        let mut kinds = vec![false; language.kind_count()];
+
+        for name in names {
+            let ids = language.kind_ids(name);
+
+            assert!(!ids.is_empty(), "Unknown node kind {name}");
+
+            for id in ids {
+                kinds[id as usize] = true;
+            }
+        }
+
+        KindSet { kinds }
+    }
+
+    pub(super) fn contains(&self, node: &Tree) -> bool {
+        self.kinds
+            .get(node.kind_id() as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+/** Formatting rules for a language, built once and shared across all files being formatted */
+pub struct FormatRules {
+    pub(super) config: FormatConfig,
+    query: FormatQuery,
+}
+
+impl FormatRules {
+    pub fn of(language: Language) -> FormatRules {
+        FormatRules {
+            config: FormatConfig::of(language),
+            query: FormatQuery::of(language),
+        }
+    }
+
+    pub fn directives(&self, tree: &Tree) -> Directives {
+        self.query.directives(tree)
+    }
+
+    /** Whether the node is a declaration, which can be documented by a Javadoc comment */
+    pub fn is_declaration(&self, node: &Tree) -> bool {
+        self.config.declarations.contains(node)
+    }
+
+    /** Whether the children of the node are statements or members laid out one per line */
+    pub fn is_block(&self, node: &Tree) -> bool {
+        node.name() == "program" || self.config.block_elements.contains(node)
     }
 }
@@ -0,0 +1,211 @@
+use std::{collections::BTreeMap, fmt, fs::read_to_string, path::Path};
+
+use crate::diagnostic::Diagnostic;
+
+/** Baseline used when `--baseline` is not given, which `--write-baseline` creates if it is missing */
+pub const DEFAULT_PATH: &str = "lint.baseline";
+
+/**
+ * Diagnostics which existed when the linter was adopted, so only new ones are reported
+ *
+ * Written as a line per fingerprint of its file, rule, hash in hex and the number of diagnostics sharing it, separated
+ * by tabs
+ */
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    entries: BTreeMap<Fingerprint, usize>,
+}
+
+/**
+ * Identifies a diagnostic by its rule, file, and a hash of the code it is reported on, rather than its position, so
+ * that changes elsewhere in the file do not affect it
+ */
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fingerprint {
+    /** Relative to the baseline, separated by `/` */
+    pub file: String,
+    pub rule: String,
+    pub hash: u64,
+}
+
+impl Fingerprint {
+    /** Hashes the reported text along with its line, ignoring indentation */
+    pub fn of(diagnostic: &Diagnostic, file: &str, source: &str) -> Fingerprint {
+        let line = source
+            .lines()
+            .nth(diagnostic.span.start.line)
+            .unwrap_or("")
+            .trim();
+        let text = &source[diagnostic.span.start_byte..diagnostic.span.end_byte];
+
+        Fingerprint {
+            file: file.to_owned(),
+            rule: diagnostic.rule.clone(),
+            hash: hash([line, "\0", text].concat().as_bytes()),
+        }
+    }
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let content = read_to_string(path)
+            .map_err(|error| format!("Unable to read {}: {error}", path.display()))?;
+
+        Baseline::parse(&content).map_err(|message| format!("{}: {message}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Baseline, String> {
+        let mut baseline = Baseline::default();
+
+        for (number, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let [file, rule, hash, count] = line.split('\t').collect::<Vec<_>>()[..] else {
+                return Err(format!(
+                    "line {}: Expected file, rule, hash and count",
+                    number + 1
+                ));
+            };
+
+            let (Ok(hash), Ok(count)) = (u64::from_str_radix(hash, 16), count.parse::<usize>())
+            else {
+                return Err(format!("line {}: Invalid hash or count", number + 1));
+            };
+
+            let fingerprint = Fingerprint {
+                file: file.to_owned(),
+                rule: rule.to_owned(),
+                hash,
+            };
+
+            *baseline.entries.entry(fingerprint).or_default() += count;
+        }
+
+        Ok(baseline)
+    }
+
+    pub fn add(&mut self, fingerprint: Fingerprint) {
+        *self.entries.entry(fingerprint).or_default() += 1;
+    }
+
+    /** Keeps the entries of the files for which `keep` holds, such as to drop those of a file before recording it again */
+    pub fn retain_files(&mut self, keep: impl Fn(&str) -> bool) {
+        self.entries
+            .retain(|fingerprint, _| keep(&fingerprint.file));
+    }
+
+    /** The diagnostics of the file which are not in the baseline, using up the entries they matched */
+    pub fn filter(
+        &mut self,
+        file: &str,
+        source: &str,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Vec<Diagnostic> {
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                match self
+                    .entries
+                    .get_mut(&Fingerprint::of(diagnostic, file, source))
+                {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                }
+            })
+            .collect()
+    }
+
+    /** Entries which were not used up by filtering, with how many of their diagnostics no longer occur */
+    pub fn remaining(&self) -> impl Iterator<Item = (&Fingerprint, usize)> {
+        self.entries
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(fingerprint, count)| (fingerprint, *count))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            formatter,
+            "# Diagnostics reported before adopting the linter, written by lint --write-baseline"
+        )?;
+
+        for (fingerprint, count) in &self.entries {
+            writeln!(
+                formatter,
+                "{}\t{}\t{:016x}\t{count}",
+                fingerprint.file, fingerprint.rule, fingerprint.hash
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/** FNV-1a, which unlike the standard library's hasher is the same across versions and platforms */
+pub(crate) fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::{language::Language, parser::Parser};
+
+    use super::*;
+    use crate::{config::Config, linter::Linter, rules};
+
+    fn lint(source: &str) -> Vec<Diagnostic> {
+        let linter = Linter::new(Language::Java, rules::all(&Config::default()).unwrap());
+
+        linter.lint(&Parser::of(Language::Java).parse(source).unwrap())
+    }
+
+    fn baseline(source: &str) -> Baseline {
+        let mut baseline = Baseline::default();
+
+        for diagnostic in lint(source) {
+            baseline.add(Fingerprint::of(&diagnostic, "A.java", source));
+        }
+
+        // Through the file format
+        Baseline::parse(&baseline.to_string()).unwrap()
+    }
+
+    const SOURCE: &str =
+        "class A {\n    boolean a(String b) {\n        return b == \"a\";\n    }\n}\n";
+
+    #[test]
+    fn matches_diagnostics_after_lines_move() {
+        let mut baseline = baseline(SOURCE);
+        let moved = SOURCE.replace("class A {\n", "class A {\n    void c() { }\n\n");
+
+        assert_eq!(baseline.len(), 1);
+        assert!(baseline.filter("A.java", &moved, lint(&moved)).is_empty());
+        assert_eq!(baseline.remaining().count(), 0);
+    }
+
+    #[test]
+    fn keeps_entries_no_longer_reported() {
+        let mut baseline = baseline(SOURCE);
+        let changed = SOURCE.replace("b == \"a\"", "b == \"c\"");
+
+        assert_eq!(baseline.filter("A.java", &changed, lint(&changed)).len(), 1);
+        assert_eq!(baseline.remaining().count(), 1);
+    }
+}
@@ -0,0 +1,251 @@
+use std::{collections::HashMap, fmt, fs::read_to_string, path::Path, str::FromStr};
+
+use parser::language::Language;
+
+use crate::{
+    diagnostic::Severity, linter::Linter, rule::Rule, rules::query::QueryRule,
+    suppression::UNUSED_SUPPRESSION,
+};
+
+/** Configuration used when `--config` is not given, if the working directory has one */
+pub const DEFAULT_PATH: &str = "lint.ini";
+
+/**
+ * Settings of rules, read from an INI style file with a section per rule id
+ *
+ * ```ini
+ * [no-system-out]
+ * query =
+ *     ((field_access) @report
+ *      (#eq? @report "System.out"))
+ * message = Use a logger rather than System.out
+ * severity = error
+ *
+ * [method-length]
+ * severity = off
+ * ```
+ *
+ * Sections with a `query` define rules, other sections set the severity and settings such as `max` of the built-in
+ * rules. A severity of `off` stops the rule from running. Indented lines continue the value of the key before them.
+ */
+#[derive(Default)]
+pub struct Config {
+    sections: Vec<(String, HashMap<String, String>)>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let content = read_to_string(path)
+            .map_err(|error| format!("Unable to read {}: {error}", path.display()))?;
+
+        Config::parse(&content).map_err(|message| format!("{}: {message}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Config, String> {
+        let mut sections: Vec<(String, HashMap<String, String>)> = vec![];
+        let mut key: Option<String> = None;
+
+        for (number, line) in content.lines().enumerate() {
+            let error = |message: &str| Err(format!("line {}: {message}", number + 1));
+
+            if line.starts_with([' ', '\t']) && !line.trim().is_empty() {
+                match (sections.last_mut(), &key) {
+                    (Some((_, settings)), Some(key)) => {
+                        let value = settings.get_mut(key).unwrap();
+                        if !value.is_empty() {
+                            value.push('\n');
+                        }
+                        value.push_str(line.trim());
+                    }
+                    _ => return error("Continuation without a key"),
+                }
+                continue;
+            }
+
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with(['#', ';']) {
+                continue;
+            }
+
+            if let Some(id) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                sections.push((id.trim().to_owned(), HashMap::new()));
+                key = None;
+                continue;
+            }
+
+            let (Some((name, value)), Some((_, settings))) =
+                (line.split_once('='), sections.last_mut())
+            else {
+                return error("Expected a [rule] section or a key = value setting");
+            };
+
+            settings.insert(name.trim().to_owned(), value.trim().to_owned());
+            key = Some(name.trim().to_owned());
+        }
+
+        Ok(Config { sections })
+    }
+
+    /** Value of the setting for the rule */
+    pub fn get(&self, rule: &str, key: &str) -> Option<&str> {
+        self.sections
+            .iter()
+            .rev()
+            .find(|(id, settings)| id == rule && settings.contains_key(key))
+            .map(|(_, settings)| settings[key].as_str())
+    }
+
+    /** Setting of the rule parsed from its text, or the default if it is not set */
+    pub fn setting<T>(&self, rule: &str, key: &str, default: T) -> Result<T, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.get(rule, key) {
+            Some(value) => {
+                T::from_str(value).map_err(|error| format!("[{rule}] Invalid {key}: {error}"))
+            }
+            None => Ok(default),
+        }
+    }
+
+    /** Rules defined by a query, compiled against the grammar of the language */
+    pub fn query_rules(&self, language: Language) -> Result<Vec<Box<dyn Rule>>, String> {
+        self.sections
+            .iter()
+            .filter(|(_, settings)| settings.contains_key("query"))
+            .map(|(id, settings)| {
+                // Rules switched off are still created, so that they are known, see configure
+                let severity = settings
+                    .get("severity")
+                    .map(|severity| parse_severity(id, severity))
+                    .transpose()?
+                    .flatten()
+                    .unwrap_or(Severity::Warning);
+
+                let message = settings
+                    .get("message")
+                    .ok_or(format!("[{id}] Query rules need a message"))?;
+
+                QueryRule::new(language, id, &settings["query"], message, severity)
+                    .map(|rule| Box::new(rule) as Box<dyn Rule>)
+                    .map_err(|message| format!("[{id}] {message}"))
+            })
+            .collect()
+    }
+
+    /**
+     * Overrides the severities of the rules, switching off those with a severity of `off`
+     *
+     * Sections of rules the linter does not have and settings the rules do not read are errors, as they would
+     * otherwise be silently ignored
+     */
+    pub fn configure(&self, linter: &mut Linter) -> Result<(), String> {
+        for (id, settings) in &self.sections {
+            let known = match linter.rule(id) {
+                Some(rule) => rule.settings(),
+                None if id == UNUSED_SUPPRESSION => vec![],
+                None => return Err(format!("[{id}] No such rule")),
+            };
+
+            let mut keys: Vec<&String> = settings.keys().collect();
+            keys.sort();
+
+            if let Some(key) = keys
+                .into_iter()
+                .find(|key| *key != "severity" && !known.contains(&key.as_str()))
+            {
+                return Err(format!("[{id}] Unknown setting {key}"));
+            }
+
+            if let Some(severity) = settings.get("severity") {
+                match parse_severity(id, severity)? {
+                    Some(severity) => linter.set_severity(id, severity),
+                    None => linter.disable(id),
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/** The severity of the rule, or None if it is switched off */
+fn parse_severity(id: &str, severity: &str) -> Result<Option<Severity>, String> {
+    if severity == "off" {
+        return Ok(None);
+    }
+
+    Severity::from_str(severity).map(Some).map_err(|_| {
+        format!("[{id}] Unknown severity {severity}, expected off, info, warning or error")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_multiline_values() {
+        let config = Config::parse(
+            "# Rules\n[a]\nquery =\n    (identifier) @report\n    ; comment\nmessage = A\n\n[b]\nseverity = error\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.get("a", "query"),
+            Some("(identifier) @report\n; comment")
+        );
+        assert_eq!(config.get("a", "message"), Some("A"));
+        assert_eq!(config.get("b", "severity"), Some("error"));
+        assert_eq!(config.get("b", "message"), None);
+    }
+
+    #[test]
+    fn rejects_settings_outside_sections() {
+        assert!(Config::parse("severity = error\n").is_err());
+    }
+
+    fn configure(content: &str) -> Result<Linter, String> {
+        let config = Config::parse(content)?;
+
+        let mut rules = crate::rules::all(&config)?;
+        rules.extend(config.query_rules(Language::Java)?);
+
+        let mut linter = Linter::new(Language::Java, rules);
+        config.configure(&mut linter)?;
+
+        Ok(linter)
+    }
+
+    #[test]
+    fn switches_off_rules() {
+        let linter = configure(
+            "[method-length]\nseverity = off\n\n[a]\nquery = (identifier) @report\nmessage = A\nseverity = off\n",
+        )
+        .unwrap();
+
+        assert!(
+            !linter
+                .rules()
+                .any(|rule| rule == "method-length" || rule == "a")
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_rules_and_settings() {
+        assert_eq!(
+            configure("[methd-length]\nmax = 10\n").err(),
+            Some("[methd-length] No such rule".to_owned())
+        );
+        assert_eq!(
+            configure("[method-length]\nmaximum = 10\n").err(),
+            Some("[method-length] Unknown setting maximum".to_owned())
+        );
+        assert!(configure("[unused-suppression]\nseverity = off\n").is_ok());
+    }
+}
@@ -0,0 +1,139 @@
+use std::{fmt, str::FromStr};
+
+use format::rewrite::Edit;
+use parser::tree::Tree;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+impl FromStr for Severity {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "info" => Ok(Severity::Info),
+            "warning" | "warn" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+/** Zero-based line and column, in bytes */
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/** Part of the source a diagnostic refers to, end exclusive */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn of(node: &Tree) -> Span {
+        let range = node.range();
+
+        Span {
+            start_byte: range.start_byte,
+            end_byte: range.end_byte,
+            start: Position {
+                line: range.start_point.row,
+                column: range.start_point.column,
+            },
+            end: Position {
+                line: range.end_point.row,
+                column: range.end_point.column,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /** Id of the rule which reported the diagnostic */
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+    /** Further explanation, such as why the code is a problem or how to address it */
+    pub notes: Vec<String>,
+    pub fix: Option<Fix>,
+}
+
+impl Diagnostic {
+    pub fn with_note(&mut self, note: impl Into<String>) -> &mut Diagnostic {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_fix(&mut self, fix: Fix) -> &mut Diagnostic {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/** One-based line and column, severity, rule and message */
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{}:{}: {}[{}] {}",
+            self.span.start.line + 1,
+            self.span.start.column + 1,
+            self.severity,
+            self.rule,
+            self.message
+        )
+    }
+}
+
+/** Replacements addressing a diagnostic, which must not overlap each other */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fix {
+    pub edits: Vec<Edit>,
+    pub applicability: Applicability,
+}
+
+impl Fix {
+    pub fn safe(edits: Vec<Edit>) -> Fix {
+        Fix {
+            edits,
+            applicability: Applicability::Safe,
+        }
+    }
+
+    /** A fix which may change behaviour, so is only applied when asked for */
+    pub fn unsafe_edits(edits: Vec<Edit>) -> Fix {
+        Fix {
+            edits,
+            applicability: Applicability::Unsafe,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Applicability {
+    /** Keeps the behaviour of the program */
+    Safe,
+    Unsafe,
+}
@@ -0,0 +1,299 @@
+/** Lines of context shown around each change */
+const CONTEXT: usize = 3;
+
+/** Edits searched for before the changed lines are shown as replaced wholesale, bounding the memory of the search */
+const MAX_EDITS: usize = 1_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Line<'text> {
+    Same(&'text str),
+    Removed(&'text str),
+    Added(&'text str),
+}
+
+/** Unified diff of the file from before to after, empty if they are the same */
+pub fn unified(path: &str, before: &str, after: &str) -> String {
+    let lines = diff_lines(
+        &before.lines().collect::<Vec<_>>(),
+        &after.lines().collect::<Vec<_>>(),
+    );
+
+    let changes: Vec<usize> = (0..lines.len())
+        .filter(|index| !matches!(lines[*index], Line::Same(_)))
+        .collect();
+
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let mut output = format!("--- {path}\n+++ {path}\n");
+
+    // Changes close enough for their context to meet share a hunk
+    let mut hunks: Vec<(usize, usize)> = vec![];
+
+    for change in changes {
+        let start = change.saturating_sub(CONTEXT);
+        let end = (change + 1 + CONTEXT).min(lines.len());
+
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    for (start, end) in hunks {
+        let old_start = lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, Line::Added(_)))
+            .count();
+        let new_start = lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, Line::Removed(_)))
+            .count();
+
+        let hunk = &lines[start..end];
+        let old_length = hunk
+            .iter()
+            .filter(|line| !matches!(line, Line::Added(_)))
+            .count();
+        let new_length = hunk
+            .iter()
+            .filter(|line| !matches!(line, Line::Removed(_)))
+            .count();
+
+        // Empty ranges are numbered by the line before them
+        let number = |start: usize, length: usize| if length == 0 { start } else { start + 1 };
+
+        output.push_str(&format!(
+            "@@ -{},{old_length} +{},{new_length} @@\n",
+            number(old_start, old_length),
+            number(new_start, new_length)
+        ));
+
+        for line in hunk {
+            let (prefix, text) = match line {
+                Line::Same(text) => (' ', text),
+                Line::Removed(text) => ('-', text),
+                Line::Added(text) => ('+', text),
+            };
+
+            output.push(prefix);
+            output.push_str(text);
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+/** Shortest edit script between the lines, after setting aside the unchanged start and end */
+fn diff_lines<'text>(before: &[&'text str], after: &[&'text str]) -> Vec<Line<'text>> {
+    let prefix = before
+        .iter()
+        .zip(after)
+        .take_while(|(before, after)| before == after)
+        .count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(before, after)| before == after)
+        .count();
+
+    let mut lines: Vec<Line> = before[..prefix]
+        .iter()
+        .map(|line| Line::Same(line))
+        .collect();
+
+    lines.extend(shortest_edit(
+        &before[prefix..before.len() - suffix],
+        &after[prefix..after.len() - suffix],
+    ));
+
+    lines.extend(
+        before[before.len() - suffix..]
+            .iter()
+            .map(|line| Line::Same(line)),
+    );
+
+    lines
+}
+
+/**
+ * Myers' greedy algorithm, taking time in proportion to the lines times the number of edits, and space to the square
+ * of the number of edits, rather than both to the product of the lengths
+ *
+ * Paths through the grid of removed by added lines move right for a removal, down for an addition and diagonally for
+ * a line in common. The furthest reaching path with each number of edits is found on every diagonal, until one reaches
+ * the end, then retraced back through the diagonals it came from.
+ */
+fn shortest_edit<'text>(removed: &[&'text str], added: &[&'text str]) -> Vec<Line<'text>> {
+    let (n, m) = (removed.len() as isize, added.len() as isize);
+    let offset = n + m + 1;
+    let diagonal = |k: isize| (k + offset) as usize;
+
+    // Furthest removed line reached on each diagonal k = x - y
+    let mut furthest = vec![0isize; diagonal(offset) + 1];
+    // Furthest lines on the diagonals around each number of edits, from before taking that many
+    let mut trace: Vec<Vec<isize>> = vec![];
+
+    'search: for d in 0..=n + m {
+        if d as usize > MAX_EDITS {
+            return removed
+                .iter()
+                .map(|line| Line::Removed(line))
+                .chain(added.iter().map(|line| Line::Added(line)))
+                .collect();
+        }
+
+        trace.push(furthest[diagonal(-d - 1)..=diagonal(d + 1)].to_vec());
+
+        for k in (-d..=d).step_by(2) {
+            let mut x =
+                if k == -d || k != d && furthest[diagonal(k - 1)] < furthest[diagonal(k + 1)] {
+                    furthest[diagonal(k + 1)]
+                } else {
+                    furthest[diagonal(k - 1)] + 1
+                };
+            let mut y = x - k;
+
+            while x < n && y < m && removed[x as usize] == added[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            furthest[diagonal(k)] = x;
+
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut x, mut y) = (n, m);
+
+    for (d, before) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let furthest = |k: isize| before[(k + d + 1) as usize];
+
+        let k = x - y;
+        let previous_k = if k == -d || k != d && furthest(k - 1) < furthest(k + 1) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = furthest(previous_k);
+        let previous_y = previous_x - previous_k;
+
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            lines.push(Line::Same(removed[x as usize]));
+        }
+
+        if d > 0 {
+            lines.push(if x == previous_x {
+                Line::Added(added[previous_y as usize])
+            } else {
+                Line::Removed(removed[previous_x as usize])
+            });
+        }
+
+        (x, y) = (previous_x, previous_y);
+    }
+
+    lines.reverse();
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_changes_with_context() {
+        let before = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let after = "a\nb\nC\nd\ne\nf\ng\nh\ni\nj\nk\n";
+
+        assert_eq!(
+            unified("A.java", before, after),
+            "--- A.java\n+++ A.java\n@@ -1,6 +1,6 @@\n a\n b\n-c\n+C\n d\n e\n f\n@@ -8,3 +8,4 @@\n h\n i\n j\n+k\n"
+        );
+    }
+
+    #[test]
+    fn keeps_lines_in_common_between_changes() {
+        let before = ["a", "b", "c", "a", "b", "b", "a"];
+        let after = ["c", "b", "a", "b", "a", "c"];
+
+        let lines = diff_lines(&before, &after);
+
+        let kept = |side: fn(&Line) -> bool| -> Vec<&str> {
+            lines
+                .iter()
+                .filter(|line| side(line))
+                .map(|(Line::Same(text) | Line::Removed(text) | Line::Added(text))| *text)
+                .collect()
+        };
+
+        assert_eq!(kept(|line| !matches!(line, Line::Added(_))), before);
+        assert_eq!(kept(|line| !matches!(line, Line::Removed(_))), after);
+        // A longest common subsequence, such as b a b a, has four lines
+        assert_eq!(kept(|line| matches!(line, Line::Same(_))).len(), 4);
+    }
+
+    #[test]
+    fn diffs_long_files_with_scattered_changes() {
+        let before: Vec<String> = (0..100_000).map(|line| line.to_string()).collect();
+        let after: Vec<String> = (0..100_000)
+            .map(|line| match line % 10_000 {
+                0 => format!("{line} changed"),
+                _ => line.to_string(),
+            })
+            .collect();
+
+        let before: Vec<&str> = before.iter().map(String::as_str).collect();
+        let after: Vec<&str> = after.iter().map(String::as_str).collect();
+
+        let lines = diff_lines(&before, &after);
+
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| matches!(line, Line::Added(_)))
+                .count(),
+            10
+        );
+    }
+
+    #[test]
+    fn replaces_lines_wholesale_past_the_maximum_edits() {
+        let before: Vec<String> = (0..MAX_EDITS).map(|line| line.to_string()).collect();
+        let after: Vec<String> = (0..MAX_EDITS)
+            .map(|line| format!("{line} changed"))
+            .collect();
+
+        let before: Vec<&str> = before.iter().map(String::as_str).collect();
+        let after: Vec<&str> = after.iter().map(String::as_str).collect();
+
+        let lines = diff_lines(&before, &after);
+
+        assert!(
+            lines[..MAX_EDITS]
+                .iter()
+                .all(|line| matches!(line, Line::Removed(_)))
+        );
+        assert!(
+            lines[MAX_EDITS..]
+                .iter()
+                .all(|line| matches!(line, Line::Added(_)))
+        );
+    }
+
+    #[test]
+    fn is_empty_without_changes() {
+        assert_eq!(unified("A.java", "a\n", "a\n"), "");
+    }
+}
@@ -0,0 +1,352 @@
+use std::ops::Range;
+
+use format::{
+    options::FormatOptions,
+    range::print_ranges,
+    render::PrettyPrintParameters,
+    rewrite::{Edit, apply},
+    transform::FormatRules,
+};
+use parser::{parser::Parser, tree::Tree};
+
+use crate::{
+    diagnostic::{Applicability, Diagnostic, Fix},
+    linter::Linter,
+};
+
+pub struct FixOptions {
+    /** Also apply fixes which may change behaviour */
+    pub unsafe_fixes: bool,
+    /** Most rounds of linting and fixing, in case fixes keep producing diagnostics with fixes */
+    pub max_iterations: usize,
+}
+
+impl Default for FixOptions {
+    fn default() -> Self {
+        FixOptions {
+            unsafe_fixes: false,
+            max_iterations: 10,
+        }
+    }
+}
+
+pub struct FixResult {
+    pub source: String,
+    /** Number of fixes applied over all rounds */
+    pub applied: usize,
+    /** Byte ranges of the fixed source which were written by fixes, so can be formatted */
+    pub touched: Vec<Range<usize>>,
+}
+
+/**
+ * Applies the fixes of the diagnostics found in the source, linting again after each round until no fixes remain
+ *
+ * Fixes overlapping a fix of an earlier diagnostic are left for the following round, where they are reported again if
+ * they still apply. Fixes which would introduce a syntax error are left out of the round.
+ */
+pub fn fix(parser: &mut Parser, linter: &Linter, source: &str, options: &FixOptions) -> FixResult {
+    let mut result = FixResult {
+        source: source.to_owned(),
+        applied: 0,
+        touched: vec![],
+    };
+
+    for _ in 0..options.max_iterations {
+        let tree = parser.parse(&result.source).unwrap();
+        let had_error = has_error(&tree);
+
+        let diagnostics = linter.lint(&tree);
+        let mut fixes = select(&diagnostics, options);
+
+        let breaks = |parser: &mut Parser, fixes: &[&Fix]| {
+            let edits = fixes.iter().flat_map(|fix| fix.edits.clone());
+
+            apply(&result.source, edits)
+                .is_some_and(|fixed| !had_error && has_error(&parser.parse(&fixed).unwrap()))
+        };
+
+        // Checking each fix on its own is only needed once they break the syntax together
+        if breaks(parser, &fixes) {
+            fixes.retain(|fix| !breaks(parser, &[fix]));
+        }
+
+        if fixes.is_empty() {
+            break;
+        }
+
+        let edits: Vec<Edit> = fixes.iter().flat_map(|fix| fix.edits.clone()).collect();
+
+        let Some(fixed) = apply(&result.source, edits.clone()) else {
+            break;
+        };
+
+        // Fixes which are fine on their own may still break the syntax together
+        if !had_error && has_error(&parser.parse(&fixed).unwrap()) {
+            break;
+        }
+
+        result.applied += fixes.len();
+        result.touched = touch(&result.touched, &edits);
+        result.source = fixed;
+    }
+
+    result
+}
+
+/** The fixed source, with the statements and members around the fixes formatted as fixes disregard layout */
+pub fn format_touched(
+    parser: &mut Parser,
+    result: &FixResult,
+    rules: &FormatRules,
+    options: &FormatOptions,
+    arguments: &PrettyPrintParameters,
+) -> String {
+    if result.touched.is_empty() {
+        return result.source.clone();
+    }
+
+    print_ranges(
+        &parser.parse(&result.source).unwrap(),
+        rules,
+        options,
+        arguments,
+        &result.touched,
+    )
+}
+
+/** Fixes which can be applied together, preferring those of earlier diagnostics */
+fn select<'diagnostics>(
+    diagnostics: &'diagnostics [Diagnostic],
+    options: &FixOptions,
+) -> Vec<&'diagnostics Fix> {
+    let mut selected: Vec<&Fix> = vec![];
+
+    for fix in diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.fix.as_ref())
+    {
+        if fix.applicability == Applicability::Unsafe && !options.unsafe_fixes {
+            continue;
+        }
+
+        let conflicts = fix.edits.iter().any(|edit| {
+            selected
+                .iter()
+                .flat_map(|other| &other.edits)
+                .any(|other| overlaps(&edit.range, &other.range))
+        });
+
+        if !conflicts {
+            selected.push(fix);
+        }
+    }
+
+    selected
+}
+
+/** Whether the ranges share a byte, or are insertions at the same offset */
+fn overlaps(first: &Range<usize>, second: &Range<usize>) -> bool {
+    first.start < second.end && second.start < first.end || first.start == second.start
+}
+
+/** The touched ranges moved past the edits, along with the ranges the edits replaced */
+fn touch(touched: &[Range<usize>], edits: &[Edit]) -> Vec<Range<usize>> {
+    // Offsets move by the change in length of the edits before them, other than the edit being moved itself
+    let shift = |offset: usize, moved: Option<usize>| -> usize {
+        edits
+            .iter()
+            .enumerate()
+            .filter(|(index, edit)| Some(*index) != moved && edit.range.end <= offset)
+            .fold(offset, |offset, (_, edit)| {
+                offset + edit.replacement.len() - edit.range.len()
+            })
+    };
+
+    let replaced = edits.iter().enumerate().map(|(index, edit)| {
+        let start = shift(edit.range.start, Some(index));
+        start..start + edit.replacement.len()
+    });
+
+    // Ranges which an edit fell within keep covering it
+    touched
+        .iter()
+        .map(|range| shift(range.start, None)..shift(range.end, None))
+        .chain(replaced)
+        .collect()
+}
+
+fn has_error(node: &Tree) -> bool {
+    node.name() == "ERROR" || node.children().iter().any(has_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::language::Language;
+
+    use super::*;
+    use crate::rule::{Context, Rule};
+
+    /** Replaces `==` with `!=` and `!=` with `<` */
+    struct Flip;
+
+    impl Rule for Flip {
+        fn id(&self) -> &str {
+            "flip"
+        }
+
+        fn kinds(&self) -> Vec<&str> {
+            vec!["==", "!="]
+        }
+
+        fn visit(&self, node: &Tree, context: &mut Context) {
+            let replacement = if node.name() == "==" { "!=" } else { "<" };
+
+            let edit = Edit {
+                range: node.range().start_byte..node.range().end_byte,
+                replacement: replacement.to_owned(),
+            };
+
+            let fix = if node.name() == "==" {
+                Fix::safe(vec![edit])
+            } else {
+                Fix::unsafe_edits(vec![edit])
+            };
+
+            context.report(node, "Flip").with_fix(fix);
+        }
+    }
+
+    /** Replaces `c` with `(`, which never parses */
+    struct Unbalance;
+
+    impl Rule for Unbalance {
+        fn id(&self) -> &str {
+            "unbalance"
+        }
+
+        fn kinds(&self) -> Vec<&str> {
+            vec!["identifier"]
+        }
+
+        fn visit(&self, node: &Tree, context: &mut Context) {
+            if node.text() == "c" {
+                let edit = Edit {
+                    range: node.range().start_byte..node.range().end_byte,
+                    replacement: "(".to_owned(),
+                };
+
+                context
+                    .report(node, "Unbalance")
+                    .with_fix(Fix::safe(vec![edit]));
+            }
+        }
+    }
+
+    fn run(source: &str, options: &FixOptions) -> FixResult {
+        let mut parser = Parser::of(Language::Java);
+        let linter = Linter::new(Language::Java, vec![Box::new(Flip)]);
+
+        fix(&mut parser, &linter, source, options)
+    }
+
+    #[test]
+    fn applies_safe_fixes_only() {
+        let result = run("class A { boolean a = b == c; }", &FixOptions::default());
+
+        assert_eq!(result.source, "class A { boolean a = b != c; }");
+        assert_eq!(result.applied, 1);
+        assert_eq!(result.touched, vec![24..26]);
+    }
+
+    #[test]
+    fn leaves_out_fixes_introducing_syntax_errors() {
+        let mut parser = Parser::of(Language::Java);
+        let linter = Linter::new(Language::Java, vec![Box::new(Flip), Box::new(Unbalance)]);
+
+        let result = fix(
+            &mut parser,
+            &linter,
+            "class A { boolean a = b == c; }",
+            &FixOptions::default(),
+        );
+
+        assert_eq!(result.source, "class A { boolean a = b != c; }");
+        assert_eq!(result.applied, 1);
+    }
+
+    #[test]
+    fn moves_touched_ranges_past_edits() {
+        let edits = [
+            Edit {
+                range: 2..2,
+                replacement: "abc".to_owned(),
+            },
+            Edit {
+                range: 5..7,
+                replacement: "d".to_owned(),
+            },
+        ];
+
+        assert_eq!(
+            touch(&[0..1, 8..10], &edits),
+            vec![0..1, 10..12, 2..5, 8..9]
+        );
+    }
+
+    #[test]
+    fn repeats_until_nothing_applies() {
+        let result = run(
+            "class A { boolean a = b == c; }",
+            &FixOptions {
+                unsafe_fixes: true,
+                ..FixOptions::default()
+            },
+        );
+
+        assert_eq!(result.source, "class A { boolean a = b < c; }");
+        assert_eq!(result.applied, 2);
+    }
+
+    #[test]
+    fn stops_at_iteration_cap() {
+        let result = run(
+            "class A { boolean a = b == c; }",
+            &FixOptions {
+                unsafe_fixes: true,
+                max_iterations: 1,
+            },
+        );
+
+        assert_eq!(result.source, "class A { boolean a = b != c; }");
+    }
+
+    #[test]
+    fn defers_overlapping_fixes() {
+        let fix = |range: Range<usize>| {
+            Fix::safe(vec![Edit {
+                range,
+                replacement: String::new(),
+            }])
+        };
+        let diagnostic = |range: Range<usize>| Diagnostic {
+            rule: "test".to_owned(),
+            severity: crate::diagnostic::Severity::Warning,
+            message: String::new(),
+            span: crate::diagnostic::Span {
+                start_byte: range.start,
+                end_byte: range.end,
+                start: Default::default(),
+                end: Default::default(),
+            },
+            notes: vec![],
+            fix: Some(fix(range)),
+        };
+
+        let diagnostics = [diagnostic(0..4), diagnostic(2..6), diagnostic(6..8)];
+
+        assert_eq!(
+            select(&diagnostics, &FixOptions::default()),
+            vec![&fix(0..4), &fix(6..8)]
+        );
+    }
+}
@@ -0,0 +1,12 @@
+pub mod baseline;
+pub mod config;
+pub mod diagnostic;
+pub mod diff;
+pub mod fix;
+pub mod linter;
+pub mod pattern;
+pub mod recipe;
+pub mod report;
+pub mod rule;
+pub mod rules;
+pub mod suppression;
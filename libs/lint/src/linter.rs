@@ -0,0 +1,314 @@
+use parser::{language::Language, tree::Tree};
+
+use crate::{
+    diagnostic::{Diagnostic, Severity},
+    rule::{Context, Rule},
+    suppression::{Suppressions, UNUSED_SUPPRESSION},
+};
+
+/** Runs the enabled rules over files of a language */
+pub struct Linter {
+    rules: Vec<EnabledRule>,
+    /** Indices of the rules visiting each kind, indexed by kind id */
+    rules_by_kind: Vec<Vec<usize>>,
+    /** Rules switched off, which are known but never run */
+    disabled: Vec<Box<dyn Rule>>,
+    /** Severity of suppressions of enabled rules which suppress nothing, or None if they are not reported */
+    unused_suppression: Option<Severity>,
+    /** Whether the built-in rules are run, which suppressions of every rule may be needed for */
+    runs_builtin_rules: bool,
+}
+
+struct EnabledRule {
+    rule: Box<dyn Rule>,
+    severity: Severity,
+}
+
+impl Linter {
+    /**
+     * Enables the rules at their default severity
+     *
+     * Panics if a rule visits a kind of node the grammar does not have, as the rule would never run
+     */
+    pub fn new(language: Language, rules: Vec<Box<dyn Rule>>) -> Linter {
+        let mut rules_by_kind = vec![Vec::new(); language.kind_count()];
+
+        for (index, rule) in rules.iter().enumerate() {
+            for kind in rule.kinds() {
+                let ids = language.kind_ids(kind);
+
+                assert!(
+                    !ids.is_empty(),
+                    "Unknown node kind {kind} visited by {}",
+                    rule.id()
+                );
+
+                for id in ids {
+                    rules_by_kind[id as usize].push(index);
+                }
+            }
+        }
+
+        Linter {
+            rules: rules
+                .into_iter()
+                .map(|rule| EnabledRule {
+                    severity: rule.default_severity(),
+                    rule,
+                })
+                .collect(),
+            rules_by_kind,
+            disabled: Vec::new(),
+            unused_suppression: Some(Severity::Warning),
+            runs_builtin_rules: true,
+        }
+    }
+
+    /** Enables rules run instead of the built-in ones, such as recipes, see new */
+    pub fn without_builtin_rules(language: Language, rules: Vec<Box<dyn Rule>>) -> Linter {
+        Linter {
+            runs_builtin_rules: false,
+            ..Linter::new(language, rules)
+        }
+    }
+
+    /** Overrides the severity a rule reports with, returning false if no such rule is enabled */
+    pub fn set_severity(&mut self, rule: &str, severity: Severity) -> bool {
+        if rule == UNUSED_SUPPRESSION {
+            self.unused_suppression = Some(severity);
+            return true;
+        }
+
+        match self
+            .rules
+            .iter_mut()
+            .find(|enabled| enabled.rule.id() == rule)
+        {
+            Some(enabled) => {
+                enabled.severity = severity;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /** Stops running a rule, returning false if no such rule is enabled */
+    pub fn disable(&mut self, rule: &str) -> bool {
+        if rule == UNUSED_SUPPRESSION {
+            self.unused_suppression = None;
+            return true;
+        }
+
+        let Some(index) = self
+            .rules
+            .iter()
+            .position(|enabled| enabled.rule.id() == rule)
+        else {
+            return false;
+        };
+
+        self.disabled.push(self.rules.remove(index).rule);
+
+        // Rules after the removed one move down
+        for indices in &mut self.rules_by_kind {
+            indices.retain(|other| *other != index);
+
+            for other in indices.iter_mut().filter(|other| **other > index) {
+                *other -= 1;
+            }
+        }
+
+        true
+    }
+
+    /** The rule with the id, whether it is enabled or not */
+    pub fn rule(&self, id: &str) -> Option<&dyn Rule> {
+        self.rules
+            .iter()
+            .map(|enabled| &enabled.rule)
+            .chain(&self.disabled)
+            .find(|rule| rule.id() == id)
+            .map(|rule| rule.as_ref())
+    }
+
+    /** Ids of the enabled rules */
+    pub fn rules(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|enabled| enabled.rule.id())
+    }
+
+    /** Diagnostics of all rules which are not suppressed, in the order of the nodes they were reported from */
+    pub fn lint(&self, program: &Tree) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut ancestors = Vec::new();
+
+        self.visit(program, &mut ancestors, &mut diagnostics);
+
+        let mut suppressions = Suppressions::of(program);
+        let mut diagnostics = suppressions.apply(diagnostics);
+
+        // Suppressions of rules which are not run, such as when running recipes, may still be needed
+        let enabled = |rule: Option<&str>| match rule {
+            Some(rule) => self.rules().any(|enabled| enabled == rule),
+            None => self.runs_builtin_rules,
+        };
+
+        // Only the built-in rules are every rule there is
+        let known = |rule: &str| {
+            !self.runs_builtin_rules || rule == UNUSED_SUPPRESSION || self.rule(rule).is_some()
+        };
+
+        if let Some(severity) = self.unused_suppression {
+            let unused = |suppressions: &Suppressions| -> Vec<Diagnostic> {
+                suppressions
+                    .unused(enabled, known)
+                    .into_iter()
+                    .map(|unused| Diagnostic {
+                        rule: UNUSED_SUPPRESSION.to_owned(),
+                        severity,
+                        message: unused.message,
+                        span: unused.span,
+                        notes: vec![unused.note.to_owned()],
+                        fix: None,
+                    })
+                    .collect()
+            };
+
+            // Unused suppressions can be suppressed like any other diagnostic, which uses the suppression doing so
+            suppressions.apply(unused(&suppressions));
+            diagnostics.extend(suppressions.apply(unused(&suppressions)));
+        }
+
+        // Rules may report on nodes other than the visited one
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.start_byte);
+
+        diagnostics
+    }
+
+    fn visit<'tree, 'source>(
+        &self,
+        node: &'tree Tree<'source>,
+        ancestors: &mut Vec<&'tree Tree<'source>>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        if let Some(indices) = self.rules_by_kind.get(node.kind_id() as usize) {
+            for index in indices {
+                let enabled = &self.rules[*index];
+
+                enabled.rule.visit(
+                    node,
+                    &mut Context {
+                        rule: enabled.rule.id(),
+                        severity: enabled.severity,
+                        ancestors,
+                        diagnostics,
+                    },
+                );
+            }
+        }
+
+        ancestors.push(node);
+
+        for child in node.children() {
+            self.visit(child, ancestors, diagnostics);
+        }
+
+        ancestors.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::parser::Parser;
+
+    use super::*;
+
+    struct EmptyBlock;
+
+    impl Rule for EmptyBlock {
+        fn id(&self) -> &str {
+            "empty-block"
+        }
+
+        fn kinds(&self) -> Vec<&str> {
+            vec!["block"]
+        }
+
+        fn visit(&self, node: &Tree, context: &mut Context) {
+            if node.children().len() == 2 {
+                let parent = context
+                    .parent()
+                    .map_or("", |parent| parent.name())
+                    .to_owned();
+
+                context
+                    .report(node, "Empty block")
+                    .with_note(format!("in {parent}"));
+            }
+        }
+    }
+
+    struct MisspelledKind;
+
+    impl Rule for MisspelledKind {
+        fn id(&self) -> &str {
+            "misspelled-kind"
+        }
+
+        fn kinds(&self) -> Vec<&str> {
+            vec!["blok"]
+        }
+
+        fn visit(&self, _node: &Tree, _context: &mut Context) {}
+    }
+
+    #[test]
+    fn reports_from_visited_kinds() {
+        let source = "class A {\n    void a() { }\n    void b() { b(); }\n}\n";
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+
+        let mut linter = Linter::new(Language::Java, vec![Box::new(EmptyBlock)]);
+        assert!(linter.set_severity("empty-block", Severity::Error));
+
+        let diagnostics = linter.lint(&tree);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "empty-block");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].span.start.line, 1);
+        assert_eq!(diagnostics[0].notes, vec!["in method_declaration"]);
+    }
+
+    #[test]
+    fn skips_disabled_rules() {
+        let source = "class A {\n    void a() { }\n}\n";
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+
+        let mut linter = Linter::new(Language::Java, vec![Box::new(EmptyBlock)]);
+        assert!(linter.disable("empty-block"));
+
+        assert!(linter.lint(&tree).is_empty());
+        assert!(linter.rule("empty-block").is_some());
+        assert!(!linter.disable("empty-block"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown node kind blok visited by misspelled-kind")]
+    fn rejects_unknown_kinds() {
+        Linter::new(Language::Java, vec![Box::new(MisspelledKind)]);
+    }
+
+    #[test]
+    fn leaves_suppressions_of_every_rule_to_the_built_in_rules() {
+        let source = "class A {\n    // lint-disable-next-line\n    void a() { a(); }\n}\n";
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+
+        let builtin = Linter::new(Language::Java, vec![Box::new(EmptyBlock)]);
+        let recipes = Linter::without_builtin_rules(Language::Java, vec![Box::new(EmptyBlock)]);
+
+        assert_eq!(builtin.lint(&tree).len(), 1);
+        assert!(recipes.lint(&tree).is_empty());
+    }
+}
@@ -0,0 +1,437 @@
+use std::{
+    collections::HashSet,
+    env::{args, current_dir, var_os},
+    ffi::OsStr,
+    fs::{read_to_string, write},
+    io::{IsTerminal, stdout},
+    path::{MAIN_SEPARATOR, Path, absolute},
+    process::exit,
+};
+
+use format::{editorconfig::EditorConfigResolver, encoding::Encoding, transform::FormatRules};
+use lint::{
+    baseline::{self, Baseline, Fingerprint},
+    config::{self, Config},
+    diagnostic::{Diagnostic, Severity},
+    diff,
+    fix::{FixOptions, fix, format_touched},
+    linter::Linter,
+    recipe::{self, Migration, Rewrite},
+    report::Format,
+    rules,
+};
+use parser::{language::Language, parser::Parser};
+use walkdir::WalkDir;
+
+const USAGE: &str = "Usage: lint [--config <file>] [--baseline <file>] [--write-baseline] [--fix | --fix-unsafe] [--dry-run] [--format <format>] <path>...\n       lint recipe <migration> [--dry-run] <path>...\n       lint recipe --list\n       lint rewrite --pattern <pattern> --replace <template> [--write] <path>...";
+
+struct Options {
+    paths: Vec<String>,
+    /** Rule settings and query rules, otherwise read from lint.ini if there is one */
+    config: Option<String>,
+    /** Diagnostics to not report, otherwise read from lint.baseline if there is one */
+    baseline: Option<String>,
+    /** Record the diagnostics in the baseline rather than reporting them */
+    write_baseline: bool,
+    /** Migration to run instead of the lint rules, which implies fixing */
+    recipe: Option<String>,
+    /** Pattern and replacement to rewrite instead of the lint rules, which implies fixing */
+    rewrite: Option<(String, String)>,
+    /** Apply the fixes of diagnostics and write the files back */
+    fix: bool,
+    /** Also apply fixes which may change behaviour */
+    fix_unsafe: bool,
+    /** Show the fixes as a diff rather than writing them */
+    dry_run: bool,
+    /** How diagnostics are written, other output going to stderr unless it is for people */
+    format: Format,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        paths: Vec::new(),
+        config: None,
+        baseline: None,
+        write_baseline: false,
+        recipe: None,
+        rewrite: None,
+        fix: false,
+        fix_unsafe: false,
+        dry_run: false,
+        format: Format::Human,
+    };
+
+    let mut args = args.by_ref().peekable();
+
+    if args.next_if(|arg| arg == "recipe").is_some() {
+        let migration = args.next().ok_or("recipe expects a migration")?;
+
+        if migration == "--list" {
+            for migration in recipe::migrations() {
+                println!("{}\t{}", migration.name, migration.description);
+            }
+            exit(0);
+        }
+
+        options.recipe = Some(migration);
+        options.fix = true;
+    }
+
+    let rewrite = args.next_if(|arg| arg == "rewrite").is_some();
+    let (mut pattern, mut replacement) = (None, None);
+
+    // Rewrites are only shown unless asked to be written
+    if rewrite {
+        options.fix = true;
+        options.dry_run = true;
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--pattern" if rewrite => {
+                pattern = Some(args.next().ok_or("--pattern expects a pattern")?)
+            }
+            "--replace" if rewrite => {
+                replacement = Some(args.next().ok_or("--replace expects a template")?)
+            }
+            "--write" if rewrite => options.dry_run = false,
+            "--config" => options.config = Some(args.next().ok_or("--config expects a file")?),
+            "--baseline" => {
+                options.baseline = Some(args.next().ok_or("--baseline expects a file")?)
+            }
+            "--write-baseline" => options.write_baseline = true,
+            "--dry-run" => options.dry_run = true,
+            "--format" => {
+                options.format = args.next().ok_or("--format expects a format")?.parse()?
+            }
+            "--fix" => options.fix = true,
+            "--fix-unsafe" => {
+                options.fix = true;
+                options.fix_unsafe = true;
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {flag}")),
+            _ => options.paths.push(arg),
+        }
+    }
+
+    if rewrite {
+        options.rewrite = Some((
+            pattern.ok_or("rewrite expects --pattern")?,
+            replacement.ok_or("rewrite expects --replace")?,
+        ));
+    }
+
+    if options.write_baseline && (options.recipe.is_some() || rewrite) {
+        return Err("--write-baseline only applies to the lint rules".to_owned());
+    }
+
+    if options.paths.is_empty() {
+        return Err("Expected at least one path".to_owned());
+    }
+
+    Ok(options)
+}
+
+fn main() {
+    let options = parse_args(args().skip(1)).unwrap_or_else(|message| {
+        eprintln!("{message}\n{USAGE}");
+        exit(2);
+    });
+
+    let mut parser = Parser::of(Language::Java);
+    let linter = match (&options.recipe, &options.rewrite) {
+        (_, Some((pattern, replacement))) => {
+            let rewrite =
+                Rewrite::new(&mut parser, pattern, replacement).unwrap_or_else(|message| {
+                    eprintln!("{message}");
+                    exit(2);
+                });
+
+            Migration {
+                name: "rewrite",
+                description: "",
+                recipes: vec![Box::new(rewrite)],
+            }
+            .into_linter(Language::Java)
+        }
+        (Some(name), None) => recipe::migrations()
+            .into_iter()
+            .find(|migration| migration.name == name)
+            .unwrap_or_else(|| {
+                eprintln!("Unknown migration {name}, see lint recipe --list");
+                exit(2);
+            })
+            .into_linter(Language::Java),
+        (None, None) => linter(&options).unwrap_or_else(|message| {
+            eprintln!("{message}");
+            exit(2);
+        }),
+    };
+    let format_rules = FormatRules::of(Language::Java);
+
+    let mut editor_config_resolver = EditorConfigResolver::new();
+
+    let baseline_path = absolute(
+        options
+            .baseline
+            .as_deref()
+            .unwrap_or(baseline::DEFAULT_PATH),
+    )
+    .unwrap();
+    let baseline_root = baseline_path.parent().unwrap().to_path_buf();
+    let mut baseline = load_baseline(&options, &baseline_path).unwrap_or_else(|message| {
+        eprintln!("{message}");
+        exit(2);
+    });
+    let mut linted = HashSet::new();
+
+    let mut reporter = options
+        .format
+        .reporter(stdout().is_terminal() && var_os("NO_COLOR").is_none());
+    let working_directory = current_dir().unwrap();
+
+    let mut errors = 0;
+
+    for arg in &options.paths {
+        let path = absolute(Path::new(arg)).unwrap();
+
+        for entry in WalkDir::new(path).sort_by_file_name() {
+            let entry = entry.unwrap();
+
+            if entry.file_type().is_file()
+                && entry.path().extension().and_then(OsStr::to_str) == Some("java")
+            {
+                let (source, diagnostics) = handle(
+                    &mut parser,
+                    &linter,
+                    &format_rules,
+                    &mut editor_config_resolver,
+                    &options,
+                    entry.path(),
+                );
+
+                let diagnostics = match &mut baseline {
+                    Some(baseline) => {
+                        let file = baseline_file(&baseline_root, entry.path());
+
+                        if options.write_baseline {
+                            baseline.retain_files(|other| other != file);
+                            for diagnostic in &diagnostics {
+                                baseline.add(Fingerprint::of(diagnostic, &file, &source));
+                            }
+                            continue;
+                        }
+
+                        let diagnostics = baseline.filter(&file, &source, diagnostics);
+                        linted.insert(file);
+                        diagnostics
+                    }
+                    None => diagnostics,
+                };
+
+                let file = entry.path();
+                let file = file.strip_prefix(&working_directory).unwrap_or(file);
+
+                print!(
+                    "{}",
+                    reporter.file(&file.display().to_string(), &source, &diagnostics)
+                );
+
+                errors += diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.severity == Severity::Error)
+                    .count();
+            }
+        }
+    }
+
+    // Files which no longer exist have no diagnostics left to record or report
+    let exists = |file: &str| baseline_root.join(file).exists();
+
+    if let Some(baseline) = &mut baseline
+        && options.write_baseline
+    {
+        baseline.retain_files(exists);
+        write(&baseline_path, baseline.to_string()).expect("Unable to write baseline");
+
+        status(
+            &options,
+            format!(
+                "Wrote {} diagnostic(s) to {}",
+                baseline.len(),
+                baseline_path.display()
+            ),
+        );
+        return;
+    }
+
+    print!("{}", reporter.finish());
+
+    for (fingerprint, count) in baseline.iter().flat_map(Baseline::remaining) {
+        if linted.contains(&fingerprint.file) || !exists(&fingerprint.file) {
+            status(
+                &options,
+                format!(
+                    "{}: {count} {} diagnostic(s) in {} no longer occur, remove them with --write-baseline",
+                    baseline_path.display(),
+                    fingerprint.rule,
+                    fingerprint.file
+                ),
+            );
+        }
+    }
+
+    if errors > 0 {
+        exit(1);
+    }
+}
+
+/** The baseline to filter or record diagnostics of the lint rules with, if there is one or one is being written */
+fn load_baseline(options: &Options, path: &Path) -> Result<Option<Baseline>, String> {
+    match &options.baseline {
+        // Recipes and rewrites report diagnostics of their own, which are never in the baseline
+        _ if options.recipe.is_some() || options.rewrite.is_some() => Ok(None),
+        _ if path.exists() => Baseline::load(path).map(Some),
+        _ if options.write_baseline => Ok(Some(Baseline::default())),
+        Some(_) => Baseline::load(path).map(Some),
+        None => Ok(None),
+    }
+}
+
+/** The path relative to the directory of the baseline, separated by `/` so the baseline is the same on every platform */
+fn baseline_file(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace(MAIN_SEPARATOR, "/")
+}
+
+/** The built-in rules along with those of the config file, configured by it */
+fn linter(options: &Options) -> Result<Linter, String> {
+    let config = match &options.config {
+        Some(path) => Config::load(Path::new(path))?,
+        None if Path::new(config::DEFAULT_PATH).exists() => {
+            Config::load(Path::new(config::DEFAULT_PATH))?
+        }
+        None => Config::default(),
+    };
+
+    let mut rules = rules::all(&config)?;
+    rules.extend(config.query_rules(Language::Java)?);
+
+    let mut linter = Linter::new(Language::Java, rules);
+    config.configure(&mut linter)?;
+
+    Ok(linter)
+}
+
+/** Lints the file, first fixing it if asked, returning the source along with the diagnostics which remain in it */
+fn handle(
+    parser: &mut Parser,
+    linter: &Linter,
+    format_rules: &FormatRules,
+    editor_config_resolver: &mut EditorConfigResolver,
+    options: &Options,
+    path: &Path,
+) -> (String, Vec<Diagnostic>) {
+    let original = read_to_string(path).unwrap();
+
+    let encoding = Encoding::detect(&original);
+    let source_code = Encoding::normalize(&original);
+
+    if !options.fix {
+        let diagnostics = linter.lint(&parser.parse(&source_code).unwrap());
+        return (source_code, diagnostics);
+    }
+
+    let fix_options = FixOptions {
+        unsafe_fixes: options.fix_unsafe,
+        // A replacement may match the pattern again, so is only made once
+        max_iterations: if options.rewrite.is_some() {
+            1
+        } else {
+            FixOptions::default().max_iterations
+        },
+    };
+
+    let fixed = fix(parser, linter, &source_code, &fix_options);
+
+    if fixed.applied == 0 {
+        let diagnostics = linter.lint(&parser.parse(&fixed.source).unwrap());
+        return (fixed.source, diagnostics);
+    }
+
+    let editorconfig = editor_config_resolver.resolve(path);
+
+    let formatted = format_touched(
+        parser,
+        &fixed,
+        format_rules,
+        &editorconfig.format_options(),
+        &editorconfig.pretty_print_parameters(),
+    );
+
+    if options.dry_run {
+        status(
+            options,
+            diff::unified(&path.display().to_string(), &source_code, &formatted)
+                .trim_end()
+                .to_owned(),
+        );
+    } else {
+        write(path, encoding.restore(&formatted)).expect("Unable to write to file");
+
+        status(
+            options,
+            format!("Applied {} fix(es) to {}", fixed.applied, path.display()),
+        );
+    }
+
+    let diagnostics = linter.lint(&parser.parse(&formatted).unwrap());
+    (formatted, diagnostics)
+}
+
+/** Writes output other than the diagnostics, which is kept out of reports read by other tools */
+fn status(options: &Options, message: String) {
+    if options.format.is_human() {
+        println!("{message}");
+    } else {
+        eprintln!("{message}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Options {
+        parse_args(args.split(' ').map(str::to_owned)).unwrap()
+    }
+
+    #[test]
+    fn recipe_implies_fix() {
+        let options = parse("recipe junit4-assert-throws src");
+
+        assert_eq!(options.recipe.as_deref(), Some("junit4-assert-throws"));
+        assert!(options.fix);
+        assert!(!options.dry_run);
+    }
+
+    #[test]
+    fn rewrite_implies_dry_run_unless_written() {
+        let options = parse("rewrite --pattern a --replace b src");
+
+        assert!(options.fix);
+        assert!(options.dry_run);
+        assert!(!parse("rewrite --pattern a --replace b --write src").dry_run);
+    }
+
+    #[test]
+    fn lints_without_fixing_by_default() {
+        let options = parse("src");
+
+        assert!(!options.fix);
+        assert!(!options.dry_run);
+        assert_eq!(options.paths, vec!["src"]);
+    }
+}
@@ -0,0 +1,362 @@
+mod template;
+
+pub use template::Template;
+
+use std::collections::HashMap;
+
+use parser::{parser::Parser, tree::Tree};
+
+use crate::rules::is_comment;
+
+/**
+ * Snippet of Java code matched against the structure of source code rather than its text
+ *
+ * Identifiers written `$name` are metavariables which match any node, and `$...name` matches any number of sibling
+ * nodes such as the arguments of a call. A metavariable used more than once must match the same text each time.
+ * Comments and layout are disregarded.
+ */
+pub struct Pattern {
+    root: Node,
+}
+
+/** Texts matched by the metavariables of a pattern, keyed by name without the `$` or `$...` */
+pub type Bindings<'source> = HashMap<String, &'source str>;
+
+enum Node {
+    Metavariable(String),
+    /** Matches any number of consecutive siblings */
+    Variadic(String),
+    Syntax {
+        name: String,
+        /** Text of leaf nodes, which must match exactly */
+        text: Option<String>,
+        children: Vec<Node>,
+    },
+}
+
+/** Valid Java identifier prefix which variadic metavariables are rewritten to before parsing */
+const VARIADIC: &str = "$$";
+
+/** Code around a pattern which parses it as an expression, a statement, a member or a whole declaration in turn */
+const CONTEXTS: [(&str, &str); 4] = [
+    ("class __Pattern { Object __pattern = ", "; }"),
+    ("class __Pattern { void __pattern() { ", " } }"),
+    ("class __Pattern { ", " }"),
+    ("", ""),
+];
+
+impl Pattern {
+    pub fn parse(parser: &mut Parser, pattern: &str) -> Result<Pattern, String> {
+        let pattern = pattern.trim().replace("$...", VARIADIC);
+
+        for (prefix, suffix) in CONTEXTS {
+            let source = format!("{prefix}{pattern}{suffix}");
+            let tree = parser.parse(&source).unwrap();
+
+            if is_malformed(&tree) {
+                continue;
+            }
+
+            let range = prefix.len()..prefix.len() + pattern.len();
+
+            if let Some(node) = outermost(&tree, &range) {
+                return match compile(node) {
+                    Node::Metavariable(_) | Node::Variadic(_) => {
+                        Err("Pattern must be more than a metavariable".to_owned())
+                    }
+                    root => Ok(Pattern { root }),
+                };
+            }
+        }
+
+        Err(format!(
+            "Pattern `{pattern}` is not a Java expression, statement or declaration"
+        ))
+    }
+
+    /** Grammar node kind which the pattern matches */
+    pub fn kind(&self) -> &str {
+        match &self.root {
+            Node::Syntax { name, .. } => name,
+            Node::Metavariable(_) | Node::Variadic(_) => unreachable!(),
+        }
+    }
+
+    /** Names of the metavariables in the pattern */
+    pub fn metavariables(&self) -> Vec<&str> {
+        let mut names = vec![];
+        let mut pending = vec![&self.root];
+
+        while let Some(node) = pending.pop() {
+            match node {
+                Node::Metavariable(name) | Node::Variadic(name) => names.push(name.as_str()),
+                Node::Syntax { children, .. } => pending.extend(children),
+            }
+        }
+
+        names
+    }
+
+    /** Bindings of the metavariables if the node matches the pattern */
+    pub fn matches<'source>(&self, node: &Tree<'source>) -> Option<Bindings<'source>> {
+        let mut bindings = Bindings::new();
+
+        match_node(&self.root, node, &mut bindings).then_some(bindings)
+    }
+}
+
+/** Whether the pattern failed to parse in the context, including tokens the parser assumed were missing */
+fn is_malformed(node: &Tree) -> bool {
+    node.name() == "ERROR"
+        || node.children().is_empty() && node.range().start_byte == node.range().end_byte
+        || node.children().iter().any(is_malformed)
+}
+
+/** Outermost node spanning exactly the range, other than the program itself */
+fn outermost<'tree, 'source>(
+    node: &'tree Tree<'source>,
+    range: &std::ops::Range<usize>,
+) -> Option<&'tree Tree<'source>> {
+    node.children()
+        .iter()
+        .find(|child| {
+            child.range().start_byte <= range.start && range.end <= child.range().end_byte
+        })
+        .and_then(|child| {
+            if child.range().start_byte == range.start && child.range().end_byte == range.end {
+                Some(child)
+            } else {
+                outermost(child, range)
+            }
+        })
+}
+
+fn compile(node: &Tree) -> Node {
+    if node.children().is_empty() && matches!(node.name(), "identifier" | "type_identifier") {
+        if let Some(name) = node.text().strip_prefix(VARIADIC) {
+            return Node::Variadic(name.to_owned());
+        }
+
+        if let Some(name) = node.text().strip_prefix('$') {
+            return Node::Metavariable(name.to_owned());
+        }
+    }
+
+    let children: Vec<Node> = node
+        .children()
+        .iter()
+        .filter(|child| !is_comment(child))
+        .map(compile)
+        .collect();
+
+    // A metavariable written as a statement matches any statement
+    if let ("expression_statement", [Node::Metavariable(name), _]) = (node.name(), &children[..]) {
+        return Node::Metavariable(name.clone());
+    }
+
+    Node::Syntax {
+        name: node.name().to_owned(),
+        text: children.is_empty().then(|| node.text().to_owned()),
+        children,
+    }
+}
+
+/** Binds the metavariable to the text, unless it is already bound to a different text */
+fn bind<'source>(name: &str, text: &'source str, bindings: &mut Bindings<'source>) -> bool {
+    match bindings.get(name) {
+        Some(bound) => *bound == text,
+        None => {
+            bindings.insert(name.to_owned(), text);
+            true
+        }
+    }
+}
+
+fn match_node<'source>(
+    pattern: &Node,
+    node: &Tree<'source>,
+    bindings: &mut Bindings<'source>,
+) -> bool {
+    match pattern {
+        Node::Metavariable(name) | Node::Variadic(name) => bind(name, span(&[node]), bindings),
+        Node::Syntax {
+            name,
+            text,
+            children,
+        } => {
+            if name != node.name() {
+                return false;
+            }
+
+            if let Some(text) = text {
+                return node.children().is_empty() && text == node.text();
+            }
+
+            let nodes: Vec<&Tree> = node
+                .children()
+                .iter()
+                .filter(|child| !is_comment(child))
+                .collect();
+
+            match match_siblings(children, &nodes, bindings.clone()) {
+                Some(matched) => {
+                    *bindings = matched;
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+}
+
+/** Matches the sequence of siblings, trying each number of siblings for variadic metavariables */
+fn match_siblings<'source>(
+    patterns: &[Node],
+    nodes: &[&Tree<'source>],
+    mut bindings: Bindings<'source>,
+) -> Option<Bindings<'source>> {
+    let Some((pattern, rest)) = patterns.split_first() else {
+        return nodes.is_empty().then_some(bindings);
+    };
+
+    if let Node::Variadic(name) = pattern {
+        return (0..=nodes.len()).find_map(|count| {
+            let mut bindings = bindings.clone();
+            bind(name, span(&nodes[..count]), &mut bindings).then_some(())?;
+
+            // The separator after matching nothing goes with it, so `f($...a, b)` matches `f(b)`
+            match rest.split_first() {
+                Some((separator, after)) if count == 0 && is_separator(separator) => {
+                    match_siblings(rest, nodes, bindings.clone())
+                        .or_else(|| match_siblings(after, nodes, bindings))
+                }
+                _ => match_siblings(rest, &nodes[count..], bindings),
+            }
+        });
+    }
+
+    // Likewise the separator before, so `f(a, $...b)` matches `f(a)`
+    let skipped = match rest.split_first() {
+        Some((Node::Variadic(name), after)) if is_separator(pattern) => {
+            let mut bindings = bindings.clone();
+            bind(name, "", &mut bindings)
+                .then(|| match_siblings(after, nodes, bindings))
+                .flatten()
+        }
+        _ => None,
+    };
+
+    skipped.or_else(|| {
+        let (node, nodes) = nodes.split_first()?;
+        match_node(pattern, node, &mut bindings).then_some(())?;
+        match_siblings(rest, nodes, bindings)
+    })
+}
+
+fn is_separator(node: &Node) -> bool {
+    matches!(node, Node::Syntax { text: Some(text), .. } if text == ",")
+}
+
+/** Source text from the first node to the last */
+fn span<'source>(nodes: &[&Tree<'source>]) -> &'source str {
+    match (nodes.first(), nodes.last()) {
+        (Some(first), Some(last)) => {
+            &first.source()[first.range().start_byte..last.range().end_byte]
+        }
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::language::Language;
+
+    use super::*;
+
+    /** Bindings of the first node matching the pattern, outermost first */
+    fn find(pattern: &str, source: &str) -> Option<Vec<(String, String)>> {
+        let mut parser = Parser::of(Language::Java);
+        let pattern = Pattern::parse(&mut parser, pattern).unwrap();
+        let tree = parser.parse(source).unwrap();
+
+        let mut pending = vec![&tree];
+
+        while let Some(node) = pending.pop() {
+            if let Some(bindings) = pattern.matches(node) {
+                let mut bindings: Vec<(String, String)> = bindings
+                    .into_iter()
+                    .map(|(name, text)| (name, text.to_owned()))
+                    .collect();
+                bindings.sort();
+                return Some(bindings);
+            }
+
+            pending.extend(node.children().iter().rev());
+        }
+
+        None
+    }
+
+    fn bound(bindings: &[(&str, &str)]) -> Option<Vec<(String, String)>> {
+        Some(
+            bindings
+                .iter()
+                .map(|(name, text)| (name.to_string(), text.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn binds_metavariables() {
+        assert_eq!(
+            find(
+                "$x.equals(\"literal\")",
+                "class A { boolean a = b.c().equals(/* text */ \"literal\"); }"
+            ),
+            bound(&[("x", "b.c()")])
+        );
+    }
+
+    #[test]
+    fn requires_repeated_metavariables_to_match() {
+        assert_eq!(find("$x == $x", "class A { boolean a = b == c; }"), None);
+        assert_eq!(
+            find("$x == $x", "class A { boolean a = b == b; }"),
+            bound(&[("x", "b")])
+        );
+    }
+
+    #[test]
+    fn binds_variadic_metavariables() {
+        let source = "class A { void a() { f(1, 2, 3); g(4); } }";
+
+        assert_eq!(find("f($...args)", source), bound(&[("args", "1, 2, 3")]));
+        assert_eq!(
+            find("f($first, $...rest)", source),
+            bound(&[("first", "1"), ("rest", "2, 3")])
+        );
+        assert_eq!(
+            find("g($first, $...rest)", source),
+            bound(&[("first", "4"), ("rest", "")])
+        );
+    }
+
+    #[test]
+    fn parses_statements() {
+        assert_eq!(
+            find(
+                "if ($condition) $body;",
+                "class A { void a() { if (b) { c(); } } }"
+            ),
+            bound(&[("body", "{ c(); }"), ("condition", "b")])
+        );
+    }
+
+    #[test]
+    fn rejects_bare_metavariables() {
+        let mut parser = Parser::of(Language::Java);
+
+        assert!(Pattern::parse(&mut parser, "$x").is_err());
+        assert!(Pattern::parse(&mut parser, "a +").is_err());
+    }
+}
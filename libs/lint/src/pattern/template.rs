@@ -0,0 +1,135 @@
+use super::{Bindings, Pattern};
+
+/** Replacement text in which the metavariables of a pattern, `$name` or `$...name`, are substituted */
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+enum Part {
+    Text(String),
+    Metavariable(String),
+    /** `$...name`, whose comma goes with it when it binds nothing */
+    Variadic(String),
+}
+
+impl Template {
+    /** Fails if the template uses metavariables the pattern does not bind */
+    pub fn parse(template: &str, pattern: &Pattern) -> Result<Template, String> {
+        let metavariables = pattern.metavariables();
+
+        let mut parts = vec![];
+        let mut rest = template;
+
+        while let Some(start) = rest.find('$') {
+            let after = &rest[start + 1..];
+            let variadic = after.starts_with("...");
+            let after = after.strip_prefix("...").unwrap_or(after);
+
+            let length = after
+                .find(|character: char| !character.is_alphanumeric() && character != '_')
+                .unwrap_or(after.len());
+
+            // A `$` not starting a name is left as is
+            if length == 0 {
+                parts.push(Part::Text(rest[..=start].to_owned()));
+                rest = &rest[start + 1..];
+                continue;
+            }
+
+            let name = &after[..length];
+
+            if !metavariables.contains(&name) {
+                return Err(format!(
+                    "Replacement uses ${name} which is not in the pattern"
+                ));
+            }
+
+            parts.push(Part::Text(rest[..start].to_owned()));
+            parts.push(if variadic {
+                Part::Variadic(name.to_owned())
+            } else {
+                Part::Metavariable(name.to_owned())
+            });
+
+            rest = &after[length..];
+        }
+
+        parts.push(Part::Text(rest.to_owned()));
+
+        Ok(Template { parts })
+    }
+
+    /** Drops the comma after, or else before, a `$...name` binding nothing, so `g($...a, b)` renders `g(b)` */
+    pub fn render(&self, bindings: &Bindings) -> String {
+        let value = |name: &str| bindings.get(name).copied().unwrap_or_default();
+
+        let mut rendered: Vec<&str> = self
+            .parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => text.as_str(),
+                Part::Metavariable(name) | Part::Variadic(name) => value(name),
+            })
+            .collect();
+
+        for (index, part) in self.parts.iter().enumerate() {
+            let Part::Variadic(name) = part else {
+                continue;
+            };
+
+            if !value(name).is_empty() {
+                continue;
+            }
+
+            let after = rendered.get(index + 1).map(|text| text.trim_start());
+
+            if let Some(after) = after.and_then(|after| after.strip_prefix(',')) {
+                rendered[index + 1] = after.trim_start();
+            } else if let Some(before) = index
+                .checked_sub(1)
+                .and_then(|before| rendered[before].trim_end().strip_suffix(','))
+            {
+                rendered[index - 1] = before;
+            }
+        }
+
+        rendered.concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::{language::Language, parser::Parser};
+
+    use super::*;
+
+    #[test]
+    fn substitutes_metavariables() {
+        let mut parser = Parser::of(Language::Java);
+        let pattern = Pattern::parse(&mut parser, "f($x, $...rest)").unwrap();
+        let template = Template::parse("g($...rest, $x, \"$\")", &pattern).unwrap();
+
+        let bindings = Bindings::from([("x".to_owned(), "a"), ("rest".to_owned(), "b, c")]);
+
+        assert_eq!(template.render(&bindings), "g(b, c, a, \"$\")");
+        assert!(Template::parse("g($y)", &pattern).is_err());
+    }
+
+    #[test]
+    fn drops_the_comma_of_empty_variadics() {
+        let mut parser = Parser::of(Language::Java);
+        let pattern = Pattern::parse(&mut parser, "f($x, $...rest)").unwrap();
+
+        let bindings = Bindings::from([("x".to_owned(), "1"), ("rest".to_owned(), "")]);
+
+        let render = |template: &str| {
+            Template::parse(template, &pattern)
+                .unwrap()
+                .render(&bindings)
+        };
+
+        assert_eq!(render("g($...rest, $x)"), "g(1)");
+        assert_eq!(render("g($x, $...rest)"), "g(1)");
+        assert_eq!(render("g($...rest)"), "g()");
+    }
+}
@@ -0,0 +1,148 @@
+use format::rewrite::Edit;
+use parser::tree::Tree;
+
+use super::{Recipe, import_path, replace};
+use crate::rule::Context;
+
+/** Replaces a type with another, in imports and wherever its simple name is used in files importing it */
+pub struct ChangeType {
+    from: String,
+    to: String,
+    description: String,
+}
+
+impl ChangeType {
+    /** Takes fully qualified names */
+    pub fn new(from: &str, to: &str) -> ChangeType {
+        ChangeType {
+            from: from.to_owned(),
+            to: to.to_owned(),
+            description: format!("Replaces {from} with {to}"),
+        }
+    }
+
+    fn simple_name(name: &str) -> &str {
+        name.rsplit('.').next().unwrap_or(name)
+    }
+
+    fn imports_type(&self, program: &Tree) -> bool {
+        program.children().iter().any(|child| {
+            child.name() == "import_declaration"
+                && !is_static(child)
+                && import_path(child).is_some_and(|path| path.text() == self.from)
+        })
+    }
+}
+
+impl Recipe for ChangeType {
+    fn name(&self) -> &str {
+        "change-type"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["import_declaration", "identifier"]
+    }
+
+    fn visit(&self, node: &Tree, context: &Context) -> Vec<Edit> {
+        if node.name() == "import_declaration" {
+            let Some(path) = import_path(node) else {
+                return vec![];
+            };
+
+            // Static imports of members of the type
+            let member = path
+                .text()
+                .strip_prefix(self.from.as_str())
+                .filter(|rest| rest.starts_with('.') && is_static(node));
+
+            return match member {
+                _ if path.text() == self.from => vec![replace(path, self.to.as_str())],
+                Some(member) => vec![replace(path, format!("{}{member}", self.to))],
+                None => vec![],
+            };
+        }
+
+        // Identifiers only name types as the name of an annotation
+        let is_type_name = node.kind() == "type_identifier"
+            || context
+                .parent()
+                .is_some_and(|parent| parent.name().ends_with("annotation"));
+
+        let from = Self::simple_name(&self.from);
+        let to = Self::simple_name(&self.to);
+
+        if !is_type_name || node.text() != from || from == to {
+            return vec![];
+        }
+
+        match context.ancestors().last() {
+            Some(program) if self.imports_type(program) => vec![replace(node, to)],
+            _ => vec![],
+        }
+    }
+}
+
+fn is_static(import: &Tree) -> bool {
+    import
+        .children()
+        .iter()
+        .any(|child| child.name() == "static")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recipe::tests::run;
+
+    #[test]
+    fn replaces_imports_and_references() {
+        let source = "import org.junit.Before;\nimport static org.junit.Before.member;\n\nclass A {\n    @Before\n    void a() { }\n}\n";
+
+        assert_eq!(
+            run(
+                vec![Box::new(ChangeType::new(
+                    "org.junit.Before",
+                    "org.junit.jupiter.api.BeforeEach"
+                ))],
+                source
+            ),
+            "import org.junit.jupiter.api.BeforeEach;\nimport static org.junit.jupiter.api.BeforeEach.member;\n\nclass A {\n    @BeforeEach\n    void a() { }\n}\n"
+        );
+    }
+
+    #[test]
+    fn replaces_type_usages() {
+        let source = "import java.util.Vector;\n\nclass A {\n    Vector<String> names = new Vector<>();\n\n    Vector<String> copy(Vector<String> other) {\n        return other;\n    }\n}\n";
+
+        assert_eq!(
+            run(
+                vec![Box::new(ChangeType::new(
+                    "java.util.Vector",
+                    "java.util.ArrayList"
+                ))],
+                source
+            ),
+            "import java.util.ArrayList;\n\nclass A {\n    ArrayList<String> names = new ArrayList<>();\n\n    ArrayList<String> copy(ArrayList<String> other) {\n        return other;\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn leaves_files_not_importing_the_type() {
+        let source = "class A {\n    @Before\n    void a() { }\n}\n";
+
+        assert_eq!(
+            run(
+                vec![Box::new(ChangeType::new(
+                    "org.junit.Before",
+                    "org.junit.jupiter.api.BeforeEach"
+                ))],
+                source
+            ),
+            source
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use format::rewrite::Edit;
+use parser::tree::Tree;
+
+use super::{Recipe, child, import_path};
+use crate::rule::Context;
+
+pub(super) const ASSERT_THROWS: &str = "org.junit.Assert.assertThrows";
+pub(super) const JUPITER_ASSERT_THROWS: &str = "org.junit.jupiter.api.Assertions.assertThrows";
+const JUPITER_TIMEOUT: &str = "org.junit.jupiter.api.Timeout";
+const TIME_UNIT: &str = "java.util.concurrent.TimeUnit";
+
+/** Replaces the `expected` element of JUnit 4's `@Test` with `assertThrows` around the body of the test */
+pub struct ExpectedExceptionToAssertThrows;
+
+impl Recipe for ExpectedExceptionToAssertThrows {
+    fn name(&self) -> &str {
+        "junit4-expected-to-assert-throws"
+    }
+
+    fn description(&self) -> &str {
+        "Replaces @Test(expected = ...) with assertThrows"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["method_declaration"]
+    }
+
+    fn visit(&self, node: &Tree, context: &Context) -> Vec<Edit> {
+        let Some(expected) = TestElement::of(node, "expected") else {
+            return vec![];
+        };
+
+        let Some(exception) = expected.value() else {
+            return vec![];
+        };
+
+        let Some(body) = child(node, "block") else {
+            return vec![];
+        };
+
+        let statements = &body.children()[1..body.children().len() - 1];
+
+        // A test expecting an exception from nothing is left to be looked at
+        let (Some(first), Some(last)) = (statements.first(), statements.last()) else {
+            return vec![];
+        };
+
+        let Some(removed) = expected.remove() else {
+            return vec![];
+        };
+
+        let mut edits = vec![removed];
+
+        let source = node.source();
+        let statements = &source[first.range().start_byte..last.range().end_byte];
+
+        edits.push(Edit {
+            range: first.range().start_byte..last.range().end_byte,
+            replacement: format!(
+                "assertThrows({}, () -> {{\n{statements}\n}});",
+                exception.text()
+            ),
+        });
+
+        if let Some(program) = context.ancestors().last()
+            && !imports(program, |path| {
+                path == ASSERT_THROWS || path == JUPITER_ASSERT_THROWS
+            })
+        {
+            // Files already using JUnit 5 import it from Jupiter, as do files migrated along with their annotations
+            let assert_throws = if imports(program, |path| path.starts_with("org.junit.jupiter.")) {
+                JUPITER_ASSERT_THROWS
+            } else {
+                ASSERT_THROWS
+            };
+
+            edits.push(add_import(
+                program,
+                &format!("import static {assert_throws};"),
+            ));
+        }
+
+        edits
+    }
+}
+
+/**
+ * Replaces the `timeout` element of JUnit 4's `@Test` with JUnit 5's `@Timeout`
+ *
+ * JUnit 4 timeouts are in milliseconds, while `@Timeout` defaults to seconds, so the unit is always given
+ */
+pub struct TimeoutToAnnotation;
+
+impl Recipe for TimeoutToAnnotation {
+    fn name(&self) -> &str {
+        "junit4-timeout-to-annotation"
+    }
+
+    fn description(&self) -> &str {
+        "Replaces @Test(timeout = ...) with @Timeout"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["method_declaration"]
+    }
+
+    fn visit(&self, node: &Tree, context: &Context) -> Vec<Edit> {
+        let Some(element) = TestElement::of(node, "timeout") else {
+            return vec![];
+        };
+
+        let (Some(timeout), Some(removed)) = (element.value(), element.remove()) else {
+            return vec![];
+        };
+
+        let start = element.annotation.range().start_byte;
+
+        let mut edits = vec![
+            removed,
+            Edit {
+                range: start..start,
+                replacement: format!(
+                    "@Timeout(value = {}, unit = TimeUnit.MILLISECONDS)\n",
+                    timeout.text()
+                ),
+            },
+        ];
+
+        if let Some(program) = context.ancestors().last() {
+            let missing: Vec<String> = [JUPITER_TIMEOUT, TIME_UNIT]
+                .into_iter()
+                .filter(|path| !imports(program, |imported| imported == *path))
+                .map(|path| format!("import {path};"))
+                .collect();
+
+            if !missing.is_empty() {
+                edits.push(add_import(program, &missing.join("\n")));
+            }
+        }
+
+        edits
+    }
+}
+
+/** An element of the `@Test` annotation of the method */
+struct TestElement<'tree, 'source> {
+    annotation: &'tree Tree<'source>,
+    arguments: &'tree Tree<'source>,
+    /** Index of the element among the children of the arguments */
+    index: usize,
+}
+
+impl<'tree, 'source> TestElement<'tree, 'source> {
+    fn of(method: &'tree Tree<'source>, name: &str) -> Option<TestElement<'tree, 'source>> {
+        child(method, "modifiers")?
+            .children()
+            .iter()
+            .filter(|annotation| annotation.name() == "annotation")
+            .filter(|annotation| {
+                child(annotation, "identifier").is_some_and(|name| name.text() == "Test")
+            })
+            .find_map(|annotation| {
+                let arguments = child(annotation, "annotation_argument_list")?;
+
+                let index = arguments.children().iter().position(|element| {
+                    element.name() == "element_value_pair"
+                        && child(element, "identifier").is_some_and(|key| key.text() == name)
+                })?;
+
+                Some(TestElement {
+                    annotation,
+                    arguments,
+                    index,
+                })
+            })
+    }
+
+    fn value(&self) -> Option<&'tree Tree<'source>> {
+        self.arguments.children()[self.index].children().last()
+    }
+
+    /** Removes the element, keeping the others along with a comma between them */
+    fn remove(&self) -> Option<Edit> {
+        let (arguments, index) = (self.arguments, self.index);
+        let elements = arguments.children();
+        let element = &elements[index];
+
+        let range = match (elements.get(index - 1), elements.get(index + 1)) {
+            (Some(open), Some(close)) if open.name() == "(" && close.name() == ")" => {
+                arguments.range().start_byte..arguments.range().end_byte
+            }
+            (_, Some(comma)) if comma.name() == "," => {
+                element.range().start_byte..elements[index + 2].range().start_byte
+            }
+            (Some(comma), _) => comma.range().start_byte..element.range().end_byte,
+            _ => return None,
+        };
+
+        Some(Edit {
+            range,
+            replacement: String::new(),
+        })
+    }
+}
+
+fn imports(program: &Tree, matches: impl Fn(&str) -> bool) -> bool {
+    program
+        .children()
+        .iter()
+        .filter(|child| child.name() == "import_declaration")
+        .filter_map(import_path)
+        .any(|path| matches(path.text()))
+}
+
+/** Inserts the imports after the last import, or the package declaration if there are none */
+fn add_import(program: &Tree, import: &str) -> Edit {
+    let find = |name: &str| {
+        program
+            .children()
+            .iter()
+            .rev()
+            .find(|child| child.name() == name)
+    };
+
+    match (find("import_declaration"), find("package_declaration")) {
+        (Some(last), _) => Edit {
+            range: last.range().end_byte..last.range().end_byte,
+            replacement: format!("\n{import}"),
+        },
+        (None, Some(package)) => Edit {
+            range: package.range().end_byte..package.range().end_byte,
+            replacement: format!("\n\n{import}"),
+        },
+        (None, None) => Edit {
+            range: 0..0,
+            replacement: format!("{import}\n\n"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recipe::tests::run;
+
+    #[test]
+    fn wraps_body_in_assert_throws() {
+        let source = "package a;\n\nclass A {\n    @Test(timeout = 5, expected = IllegalArgumentException.class)\n    public void rejects() {\n        parse(\"x\");\n        parse(\"y\");\n    }\n}\n";
+
+        assert_eq!(
+            run(vec![Box::new(ExpectedExceptionToAssertThrows)], source),
+            "package a;\n\nimport static org.junit.Assert.assertThrows;\n\nclass A {\n    @Test(timeout = 5)\n    public void rejects() {\n        assertThrows(IllegalArgumentException.class, () -> {\n            parse(\"x\");\n            parse(\"y\");\n        });\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn imports_assert_throws_from_jupiter_alongside_jupiter() {
+        let source = "import org.junit.Test;\nimport static org.junit.jupiter.api.Assertions.assertEquals;\n\nclass A {\n    @Test(expected = E.class)\n    public void fails() {\n        go();\n    }\n}\n";
+
+        assert_eq!(
+            run(vec![Box::new(ExpectedExceptionToAssertThrows)], source),
+            "import org.junit.Test;\nimport static org.junit.jupiter.api.Assertions.assertEquals;\nimport static org.junit.jupiter.api.Assertions.assertThrows;\n\nclass A {\n    @Test\n    public void fails() {\n        assertThrows(E.class, () -> {\n            go();\n        });\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn replaces_timeout_with_annotation() {
+        let source = "import java.util.concurrent.TimeUnit;\n\nclass A {\n    @Test(timeout = 5)\n    public void a() {\n        go();\n    }\n}\n";
+
+        assert_eq!(
+            run(vec![Box::new(TimeoutToAnnotation)], source),
+            "import java.util.concurrent.TimeUnit;\nimport org.junit.jupiter.api.Timeout;\n\nclass A {\n    @Timeout(value = 5, unit = TimeUnit.MILLISECONDS)\n    @Test\n    public void a() {\n        go();\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn leaves_tests_without_expected() {
+        let source =
+            "class A {\n    @Test(timeout = 5)\n    public void a() {\n        go();\n    }\n}\n";
+
+        assert_eq!(
+            run(vec![Box::new(ExpectedExceptionToAssertThrows)], source),
+            source
+        );
+    }
+}
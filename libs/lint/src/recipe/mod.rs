@@ -0,0 +1,188 @@
+mod change_type;
+mod junit;
+mod rewrite;
+
+pub use change_type::ChangeType;
+pub use junit::{ExpectedExceptionToAssertThrows, TimeoutToAnnotation};
+pub use rewrite::Rewrite;
+
+use format::rewrite::Edit;
+use parser::{language::Language, tree::Tree};
+
+use crate::{
+    diagnostic::Fix,
+    linter::Linter,
+    recipe::junit::{ASSERT_THROWS, JUPITER_ASSERT_THROWS},
+    rule::{Context, Rule},
+    rules::child,
+};
+
+/**
+ * A structural change to source code, in the style of OpenRewrite recipes
+ *
+ * Recipes are run like rules whose diagnostics always carry a fix, so share the conflict resolution, re-parsing and
+ * formatting of fixes
+ */
+pub trait Recipe {
+    fn name(&self) -> &str;
+
+    fn description(&self) -> &str;
+
+    /** Grammar node kinds the recipe is visited with */
+    fn kinds(&self) -> Vec<&str>;
+
+    /** Edits rewriting the node, which are applied together, or none if the node is left as is */
+    fn visit(&self, node: &Tree, context: &Context) -> Vec<Edit>;
+}
+
+/** Named set of recipes run together, such as the steps of migrating to a new version of a library */
+pub struct Migration {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub recipes: Vec<Box<dyn Recipe>>,
+}
+
+impl Migration {
+    pub fn into_linter(self, language: Language) -> Linter {
+        Linter::without_builtin_rules(
+            language,
+            self.recipes
+                .into_iter()
+                .map(|recipe| Box::new(RecipeRule(recipe)) as Box<dyn Rule>)
+                .collect(),
+        )
+    }
+}
+
+/** The built-in migrations */
+pub fn migrations() -> Vec<Migration> {
+    let junit5 = |from: &str, to: &str| -> Box<dyn Recipe> {
+        Box::new(ChangeType::new(
+            &format!("org.junit.{from}"),
+            &format!("org.junit.jupiter.api.{to}"),
+        ))
+    };
+
+    vec![
+        Migration {
+            name: "junit4-assert-throws",
+            description: "Replaces @Test(expected = ...) with assertThrows",
+            recipes: vec![Box::new(ExpectedExceptionToAssertThrows)],
+        },
+        Migration {
+            name: "junit5-annotations",
+            description: "Replaces the JUnit 4 test and lifecycle annotations with their JUnit 5 equivalents",
+            // The elements of JUnit 4's @Test have no equivalent in JUnit 5's, so are replaced before it is
+            recipes: vec![
+                Box::new(ExpectedExceptionToAssertThrows),
+                Box::new(TimeoutToAnnotation),
+                junit5("Test", "Test"),
+                junit5("Before", "BeforeEach"),
+                junit5("After", "AfterEach"),
+                junit5("BeforeClass", "BeforeAll"),
+                junit5("AfterClass", "AfterAll"),
+                junit5("Ignore", "Disabled"),
+                // Added by junit4-assert-throws, which has the same signature in JUnit 5
+                Box::new(ChangeType::new(ASSERT_THROWS, JUPITER_ASSERT_THROWS)),
+            ],
+        },
+    ]
+}
+
+struct RecipeRule(Box<dyn Recipe>);
+
+impl Rule for RecipeRule {
+    fn id(&self) -> &str {
+        self.0.name()
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        self.0.kinds()
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let edits = self.0.visit(node, context);
+
+        if !edits.is_empty() {
+            context
+                .report(node, self.0.description())
+                .with_fix(Fix::safe(edits));
+        }
+    }
+}
+
+/** Edit replacing the whole node */
+fn replace(node: &Tree, replacement: impl Into<String>) -> Edit {
+    Edit {
+        range: node.range().start_byte..node.range().end_byte,
+        replacement: replacement.into(),
+    }
+}
+
+/** Dotted name of an import, without `static` or a trailing `.*` */
+fn import_path<'tree, 'source>(import: &'tree Tree<'source>) -> Option<&'tree Tree<'source>> {
+    child(import, "scoped_identifier").or_else(|| child(import, "identifier"))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use format::{options::FormatOptions, render::PrettyPrintParameters, transform::FormatRules};
+    use parser::parser::Parser;
+
+    use super::*;
+    use crate::fix::{FixOptions, fix, format_touched};
+
+    pub(crate) fn run(recipes: Vec<Box<dyn Recipe>>, source: &str) -> String {
+        let linter = Migration {
+            name: "test",
+            description: "",
+            recipes,
+        }
+        .into_linter(Language::Java);
+
+        let mut parser = Parser::of(Language::Java);
+        let fixed = fix(&mut parser, &linter, source, &FixOptions::default());
+
+        format_touched(
+            &mut parser,
+            &fixed,
+            &FormatRules::of(Language::Java),
+            &FormatOptions::default(),
+            &PrettyPrintParameters {
+                indent_size: 4,
+                max_line_length: 100,
+            },
+        )
+    }
+
+    #[test]
+    fn runs_migration_recipes_together() {
+        let source = "import org.junit.Before;\nimport org.junit.Test;\n\nclass A {\n    @Before\n    public void setUp() { }\n\n    @Test(expected = E.class)\n    public void fails() {\n        go();\n    }\n}\n";
+
+        let recipes = migrations()
+            .into_iter()
+            .flat_map(|migration| migration.recipes)
+            .collect();
+
+        assert_eq!(
+            run(recipes, source),
+            "import org.junit.jupiter.api.BeforeEach;\nimport org.junit.jupiter.api.Test;\nimport static org.junit.jupiter.api.Assertions.assertThrows;\n\nclass A {\n    @BeforeEach\n    public void setUp() { }\n\n    @Test\n    public void fails() {\n        assertThrows(E.class, () -> {\n            go();\n        });\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn migrates_test_elements_along_with_annotations() {
+        let source = "import org.junit.Test;\n\nclass A {\n    @Test(expected = E.class, timeout = 100)\n    public void fails() {\n        go();\n    }\n}\n";
+
+        let recipes = migrations()
+            .into_iter()
+            .find(|migration| migration.name == "junit5-annotations")
+            .unwrap()
+            .recipes;
+
+        assert_eq!(
+            run(recipes, source),
+            "import org.junit.jupiter.api.Test;\nimport static org.junit.jupiter.api.Assertions.assertThrows;\nimport org.junit.jupiter.api.Timeout;\nimport java.util.concurrent.TimeUnit;\n\nclass A {\n    @Timeout(value = 100, unit = TimeUnit.MILLISECONDS)\n    @Test\n    public void fails() {\n        assertThrows(E.class, () -> {\n            go();\n        });\n    }\n}\n"
+        );
+    }
+}
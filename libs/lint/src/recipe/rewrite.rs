@@ -0,0 +1,75 @@
+use format::rewrite::Edit;
+use parser::{parser::Parser, tree::Tree};
+
+use super::{Recipe, replace};
+use crate::{
+    pattern::{Pattern, Template},
+    rule::Context,
+};
+
+/** Replaces code matching a pattern with the template, filled in with what the metavariables matched */
+pub struct Rewrite {
+    pattern: Pattern,
+    template: Template,
+    description: String,
+}
+
+impl Rewrite {
+    pub fn new(parser: &mut Parser, pattern: &str, replacement: &str) -> Result<Rewrite, String> {
+        let compiled = Pattern::parse(parser, pattern)?;
+        let template = Template::parse(replacement, &compiled)?;
+
+        Ok(Rewrite {
+            pattern: compiled,
+            template,
+            description: format!("Replaces `{pattern}` with `{replacement}`"),
+        })
+    }
+}
+
+impl Recipe for Rewrite {
+    fn name(&self) -> &str {
+        "rewrite"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec![self.pattern.kind()]
+    }
+
+    fn visit(&self, node: &Tree, _context: &Context) -> Vec<Edit> {
+        match self.pattern.matches(node) {
+            Some(bindings) => vec![replace(node, self.template.render(&bindings))],
+            None => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::language::Language;
+
+    use super::*;
+    use crate::recipe::tests::run;
+
+    #[test]
+    fn swaps_equals_receiver() {
+        let rewrite = Rewrite::new(
+            &mut Parser::of(Language::Java),
+            "$x.equals(\"literal\")",
+            "\"literal\".equals($x)",
+        )
+        .unwrap();
+
+        assert_eq!(
+            run(
+                vec![Box::new(rewrite)],
+                "class A {\n    boolean a(String b) {\n        return b.trim().equals(\"literal\");\n    }\n}\n"
+            ),
+            "class A {\n    boolean a(String b) {\n        return \"literal\".equals(b.trim());\n    }\n}\n"
+        );
+    }
+}
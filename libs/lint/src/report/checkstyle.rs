@@ -0,0 +1,55 @@
+use std::fmt::Write;
+
+use super::{Location, Reporter, xml_escape};
+use crate::diagnostic::Diagnostic;
+
+/** The XML written by Checkstyle, with an element per linted file */
+#[derive(Default)]
+pub struct Checkstyle {
+    files: String,
+}
+
+impl Reporter for Checkstyle {
+    fn file(&mut self, file: &str, source: &str, diagnostics: &[Diagnostic]) -> String {
+        writeln!(self.files, "  <file name=\"{}\">", xml_escape(file)).unwrap();
+
+        for diagnostic in diagnostics {
+            let location = Location::of(&diagnostic.span, source);
+
+            writeln!(
+                self.files,
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"lint.{}\"/>",
+                location.line,
+                location.column,
+                diagnostic.severity,
+                xml_escape(&diagnostic.message),
+                xml_escape(&diagnostic.rule)
+            )
+            .unwrap();
+        }
+
+        self.files.push_str("  </file>\n");
+
+        String::new()
+    }
+
+    fn finish(&mut self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n{}</checkstyle>\n",
+            self.files
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{Format, tests::report};
+
+    #[test]
+    fn writes_every_file() {
+        assert_eq!(
+            report(Format::Checkstyle),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n  <file name=\"src/A.java\">\n    <error line=\"3\" column=\"16\" severity=\"error\" message=\"Strings compared by reference\" source=\"lint.string-equality\"/>\n  </file>\n  <file name=\"src/B.java\">\n  </file>\n</checkstyle>\n"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use super::{Location, Reporter};
+use crate::diagnostic::{Diagnostic, Severity};
+
+/** `::error file=...::message` workflow commands, which GitHub Actions turns into annotations */
+pub struct GithubAnnotations;
+
+impl Reporter for GithubAnnotations {
+    fn file(&mut self, file: &str, source: &str, diagnostics: &[Diagnostic]) -> String {
+        diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let location = Location::of(&diagnostic.span, source);
+                let command = match diagnostic.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Info => "notice",
+                };
+
+                format!(
+                    "::{command} file={},line={},col={},endLine={},endColumn={},title={}::{}\n",
+                    escape_property(file),
+                    location.line,
+                    location.column,
+                    location.end_line,
+                    location.end_column,
+                    escape_property(&diagnostic.rule),
+                    escape_data(&diagnostic.message)
+                )
+            })
+            .collect()
+    }
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/** Properties are also separated by commas, and from the message by colons */
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{Format, tests::report};
+
+    #[test]
+    fn writes_workflow_commands() {
+        assert_eq!(
+            report(Format::GithubAnnotations),
+            "::error file=src/A.java,line=3,col=16,endLine=3,endColumn=24,title=string-equality::Strings compared by reference\n"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use super::{Reporter, json_string};
+use crate::{
+    baseline::{Fingerprint, hash},
+    diagnostic::{Diagnostic, Severity},
+};
+
+/** GitLab Code Quality issues, which are compared between pipelines by their fingerprints */
+#[derive(Default)]
+pub struct Gitlab {
+    issues: Vec<String>,
+    /** How many times each fingerprint has been seen, to keep those of identical diagnostics distinct */
+    seen: HashMap<String, usize>,
+}
+
+impl Reporter for Gitlab {
+    fn file(&mut self, file: &str, source: &str, diagnostics: &[Diagnostic]) -> String {
+        for diagnostic in diagnostics {
+            // The baseline fingerprint only hashes the code, so the file and rule are hashed along with it
+            let fingerprint = Fingerprint::of(diagnostic, file, source);
+            let fingerprint = format!(
+                "{:016x}",
+                hash(
+                    format!(
+                        "{}\0{}\0{:x}",
+                        fingerprint.file, fingerprint.rule, fingerprint.hash
+                    )
+                    .as_bytes()
+                )
+            );
+
+            let seen = self.seen.entry(fingerprint.clone()).or_default();
+            *seen += 1;
+            let fingerprint = match seen {
+                1 => fingerprint,
+                _ => format!("{fingerprint}-{seen}"),
+            };
+
+            let severity = match diagnostic.severity {
+                Severity::Error => "major",
+                Severity::Warning => "minor",
+                Severity::Info => "info",
+            };
+
+            self.issues.push(format!(
+                "{{\"type\":\"issue\",\"description\":{},\"check_name\":{},\"fingerprint\":\"{fingerprint}\",\"severity\":\"{severity}\",\"location\":{{\"path\":{},\"lines\":{{\"begin\":{}}}}}}}",
+                json_string(&diagnostic.message),
+                json_string(&diagnostic.rule),
+                json_string(file),
+                diagnostic.span.start.line + 1
+            ));
+        }
+
+        String::new()
+    }
+
+    fn finish(&mut self) -> String {
+        format!("[{}]\n", self.issues.join(",\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{Format, tests::report};
+
+    #[test]
+    fn writes_code_quality_issues() {
+        let report = report(Format::Gitlab);
+
+        assert!(report.starts_with("[{\"type\":\"issue\",\"description\":\"Strings compared by reference\",\"check_name\":\"string-equality\",\"fingerprint\":\""));
+        assert!(report.ends_with("\"severity\":\"major\",\"location\":{\"path\":\"src/A.java\",\"lines\":{\"begin\":3}}}]\n"));
+    }
+}
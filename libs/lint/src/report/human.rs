@@ -0,0 +1,118 @@
+use std::fmt::Write;
+
+use super::{Location, Reporter};
+use crate::diagnostic::{Diagnostic, Severity};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const CYAN: &str = "\x1b[1;36m";
+const BLUE: &str = "\x1b[1;34m";
+
+/** Each diagnostic with the line it is on, and carets under the reported code */
+pub struct Human {
+    color: bool,
+}
+
+impl Human {
+    pub fn new(color: bool) -> Human {
+        Human { color }
+    }
+
+    fn paint(&self, color: &str, text: &str) -> String {
+        if self.color {
+            format!("{color}{text}{RESET}")
+        } else {
+            text.to_owned()
+        }
+    }
+}
+
+impl Reporter for Human {
+    fn file(&mut self, file: &str, source: &str, diagnostics: &[Diagnostic]) -> String {
+        let mut output = String::new();
+
+        for diagnostic in diagnostics {
+            let location = Location::of(&diagnostic.span, source);
+            let color = match diagnostic.severity {
+                Severity::Error => RED,
+                Severity::Warning => YELLOW,
+                Severity::Info => CYAN,
+            };
+
+            let line = source.lines().nth(diagnostic.span.start.line).unwrap_or("");
+            let start = diagnostic.span.start.column.min(line.len());
+
+            // Spans over several lines are underlined to the end of the first
+            let end = if location.end_line == location.line {
+                diagnostic.span.end.column.clamp(start, line.len())
+            } else {
+                line.len()
+            };
+
+            // Tabs are kept so the carets line up however wide they are shown
+            let indent: String = line[..start]
+                .chars()
+                .map(|character| if character == '\t' { '\t' } else { ' ' })
+                .collect();
+            let carets = "^".repeat(line[start..end].chars().count().max(1));
+
+            let gutter = " ".repeat(location.line.to_string().len());
+            let bar = self.paint(BLUE, "|");
+
+            writeln!(
+                output,
+                "{}: {}",
+                self.paint(
+                    color,
+                    &format!("{}[{}]", diagnostic.severity, diagnostic.rule)
+                ),
+                self.paint(BOLD, &diagnostic.message)
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "{gutter}{} {file}:{}:{}",
+                self.paint(BLUE, "-->"),
+                location.line,
+                location.column
+            )
+            .unwrap();
+            writeln!(output, "{gutter} {bar}").unwrap();
+            writeln!(
+                output,
+                "{} {bar} {line}",
+                self.paint(BLUE, &location.line.to_string())
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "{gutter} {bar} {indent}{}",
+                self.paint(color, &carets)
+            )
+            .unwrap();
+
+            for note in &diagnostic.notes {
+                writeln!(output, "{gutter} {} note: {note}", self.paint(BLUE, "=")).unwrap();
+            }
+
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{Format, tests::report};
+
+    #[test]
+    fn underlines_the_span() {
+        assert_eq!(
+            report(Format::Human),
+            "error[string-equality]: Strings compared by reference\n --> src/A.java:3:16\n  |\n3 |         return b == \"a\";\n  |                ^^^^^^^^\n  = note: Use equals to compare their contents\n\n"
+        );
+    }
+}
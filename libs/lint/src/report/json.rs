@@ -0,0 +1,54 @@
+use super::{Location, Reporter, json_string};
+use crate::diagnostic::Diagnostic;
+
+/** An array of every diagnostic, with one-based positions */
+#[derive(Default)]
+pub struct Json {
+    entries: Vec<String>,
+}
+
+impl Reporter for Json {
+    fn file(&mut self, file: &str, source: &str, diagnostics: &[Diagnostic]) -> String {
+        for diagnostic in diagnostics {
+            let location = Location::of(&diagnostic.span, source);
+            let notes: Vec<String> = diagnostic
+                .notes
+                .iter()
+                .map(|note| json_string(note))
+                .collect();
+
+            self.entries.push(format!(
+                "{{\"file\":{},\"rule\":{},\"severity\":\"{}\",\"message\":{},\"line\":{},\"column\":{},\"endLine\":{},\"endColumn\":{},\"notes\":[{}],\"fixable\":{}}}",
+                json_string(file),
+                json_string(&diagnostic.rule),
+                diagnostic.severity,
+                json_string(&diagnostic.message),
+                location.line,
+                location.column,
+                location.end_line,
+                location.end_column,
+                notes.join(","),
+                diagnostic.fix.is_some()
+            ));
+        }
+
+        String::new()
+    }
+
+    fn finish(&mut self) -> String {
+        format!("[{}]\n", self.entries.join(",\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{Format, tests::report};
+
+    #[test]
+    fn writes_an_array() {
+        assert_eq!(
+            report(Format::Json),
+            "[{\"file\":\"src/A.java\",\"rule\":\"string-equality\",\"severity\":\"error\",\"message\":\"Strings compared by reference\",\"line\":3,\"column\":16,\"endLine\":3,\"endColumn\":24,\"notes\":[\"Use equals to compare their contents\"],\"fixable\":true}]\n"
+        );
+    }
+}
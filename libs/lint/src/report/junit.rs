@@ -0,0 +1,78 @@
+use std::fmt::Write;
+
+use super::{Location, Reporter, xml_escape};
+use crate::diagnostic::Diagnostic;
+
+/** A test suite per linted file, with a failing test case per diagnostic or a passing one if there are none */
+#[derive(Default)]
+pub struct Junit {
+    suites: String,
+    tests: usize,
+    failures: usize,
+}
+
+impl Reporter for Junit {
+    fn file(&mut self, file: &str, source: &str, diagnostics: &[Diagnostic]) -> String {
+        let file = xml_escape(file);
+        let tests = diagnostics.len().max(1);
+
+        self.tests += tests;
+        self.failures += diagnostics.len();
+
+        writeln!(
+            self.suites,
+            "  <testsuite name=\"{file}\" tests=\"{tests}\" failures=\"{}\" errors=\"0\">",
+            diagnostics.len()
+        )
+        .unwrap();
+
+        if diagnostics.is_empty() {
+            writeln!(
+                self.suites,
+                "    <testcase classname=\"{file}\" name=\"lint\"/>"
+            )
+            .unwrap();
+        }
+
+        for diagnostic in diagnostics {
+            let location = Location::of(&diagnostic.span, source);
+            let message = xml_escape(&diagnostic.message);
+
+            writeln!(
+                self.suites,
+                "    <testcase classname=\"{file}\" name=\"{}:{} {}\">\n      <failure message=\"{message}\" type=\"{}\">{file}:{}:{}: {message}</failure>\n    </testcase>",
+                location.line,
+                location.column,
+                xml_escape(&diagnostic.rule),
+                diagnostic.severity,
+                location.line,
+                location.column
+            )
+            .unwrap();
+        }
+
+        self.suites.push_str("  </testsuite>\n");
+
+        String::new()
+    }
+
+    fn finish(&mut self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"lint\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n{}</testsuites>\n",
+            self.tests, self.failures, self.suites
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{Format, tests::report};
+
+    #[test]
+    fn fails_a_test_case_per_diagnostic() {
+        assert_eq!(
+            report(Format::Junit),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"lint\" tests=\"2\" failures=\"1\" errors=\"0\">\n  <testsuite name=\"src/A.java\" tests=\"1\" failures=\"1\" errors=\"0\">\n    <testcase classname=\"src/A.java\" name=\"3:16 string-equality\">\n      <failure message=\"Strings compared by reference\" type=\"error\">src/A.java:3:16: Strings compared by reference</failure>\n    </testcase>\n  </testsuite>\n  <testsuite name=\"src/B.java\" tests=\"1\" failures=\"0\" errors=\"0\">\n    <testcase classname=\"src/B.java\" name=\"lint\"/>\n  </testsuite>\n</testsuites>\n"
+        );
+    }
+}
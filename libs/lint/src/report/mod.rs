@@ -0,0 +1,199 @@
+mod checkstyle;
+mod github;
+mod gitlab;
+mod human;
+mod json;
+mod junit;
+mod sarif;
+
+use std::str::FromStr;
+
+use crate::diagnostic::{Diagnostic, Span};
+
+/** How diagnostics are written, chosen with `--format` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /** Code frames with carets under each diagnostic */
+    Human,
+    Json,
+    /** Static Analysis Results Interchange Format, read by code scanning dashboards */
+    Sarif,
+    Checkstyle,
+    Junit,
+    /** GitLab Code Quality */
+    Gitlab,
+    /** Workflow commands which GitHub Actions shows on the changed lines */
+    GithubAnnotations,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            "sarif" => Ok(Format::Sarif),
+            "checkstyle" => Ok(Format::Checkstyle),
+            "junit" => Ok(Format::Junit),
+            "gitlab" => Ok(Format::Gitlab),
+            "github-annotations" => Ok(Format::GithubAnnotations),
+            _ => Err(format!(
+                "Unknown format {value}, expected human, json, sarif, checkstyle, junit, gitlab or github-annotations"
+            )),
+        }
+    }
+}
+
+impl Format {
+    /** Colors are only used by the human format */
+    pub fn reporter(self, color: bool) -> Box<dyn Reporter> {
+        match self {
+            Format::Human => Box::new(human::Human::new(color)),
+            Format::Json => Box::<json::Json>::default(),
+            Format::Sarif => Box::<sarif::Sarif>::default(),
+            Format::Checkstyle => Box::<checkstyle::Checkstyle>::default(),
+            Format::Junit => Box::<junit::Junit>::default(),
+            Format::Gitlab => Box::<gitlab::Gitlab>::default(),
+            Format::GithubAnnotations => Box::new(github::GithubAnnotations),
+        }
+    }
+
+    /** Whether the output is only read by people, so other messages can be written alongside it */
+    pub fn is_human(self) -> bool {
+        self == Format::Human
+    }
+}
+
+/**
+ * Writes diagnostics as each file is linted, returning the output rather than printing it
+ *
+ * Formats which are a single document only write it once every file has been linted
+ */
+pub trait Reporter {
+    /** The diagnostics of the file, which is given relative to where the linter is run */
+    fn file(&mut self, file: &str, source: &str, diagnostics: &[Diagnostic]) -> String;
+
+    fn finish(&mut self) -> String {
+        String::new()
+    }
+}
+
+/** One-based lines and columns, counted in characters as most tools reading reports expect */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Location {
+    pub fn of(span: &Span, source: &str) -> Location {
+        let column = |byte: usize| {
+            let line_start = source[..byte].rfind('\n').map_or(0, |newline| newline + 1);
+            source[line_start..byte].chars().count() + 1
+        };
+
+        Location {
+            line: span.start.line + 1,
+            column: column(span.start_byte),
+            end_line: span.end.line + 1,
+            end_column: column(span.end_byte),
+        }
+    }
+}
+
+/** Quoted, with the characters JSON does not allow in strings escaped */
+pub(crate) fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            control if control.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", control as u32))
+            }
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+/** Escaped for use in XML text and attribute values */
+pub(crate) fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use parser::{language::Language, parser::Parser};
+
+    use super::*;
+    use crate::{config::Config, linter::Linter, rules};
+
+    pub const SOURCE: &str =
+        "class A {\n    boolean a(String b) {\n        return b == \"a\";\n    }\n}\n";
+
+    /** Reports the diagnostics of `SOURCE` in `src/A.java`, and another file without any */
+    pub fn report(format: Format) -> String {
+        let linter = Linter::new(Language::Java, rules::all(&Config::default()).unwrap());
+        let clean = "class B { }\n";
+
+        let mut parser = Parser::of(Language::Java);
+        let mut reporter = format.reporter(false);
+
+        let mut output = reporter.file(
+            "src/A.java",
+            SOURCE,
+            &linter.lint(&parser.parse(SOURCE).unwrap()),
+        );
+        output += &reporter.file(
+            "src/B.java",
+            clean,
+            &linter.lint(&parser.parse(clean).unwrap()),
+        );
+        output += &reporter.finish();
+
+        output
+    }
+
+    #[test]
+    fn counts_columns_in_characters() {
+        let source = "// é\nint  é = 1;\n";
+        let start_byte = source.rfind('é').unwrap();
+
+        let span = Span {
+            start_byte,
+            end_byte: start_byte + 'é'.len_utf8(),
+            start: crate::diagnostic::Position { line: 1, column: 5 },
+            end: crate::diagnostic::Position { line: 1, column: 7 },
+        };
+
+        assert_eq!(
+            Location::of(&span, source),
+            Location {
+                line: 2,
+                column: 6,
+                end_line: 2,
+                end_column: 7
+            }
+        );
+    }
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(json_string("a \"b\"\n\\"), "\"a \\\"b\\\"\\n\\\\\"");
+    }
+}
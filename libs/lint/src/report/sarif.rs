@@ -0,0 +1,67 @@
+use std::collections::BTreeSet;
+
+use super::{Location, Reporter, json_string};
+use crate::diagnostic::{Diagnostic, Severity};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/** A SARIF 2.1.0 log with a single run, listing the rules which reported anything */
+#[derive(Default)]
+pub struct Sarif {
+    results: Vec<String>,
+    rules: BTreeSet<String>,
+}
+
+impl Reporter for Sarif {
+    fn file(&mut self, file: &str, source: &str, diagnostics: &[Diagnostic]) -> String {
+        for diagnostic in diagnostics {
+            let location = Location::of(&diagnostic.span, source);
+            let level = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "note",
+            };
+
+            self.rules.insert(diagnostic.rule.clone());
+            self.results.push(format!(
+                "{{\"ruleId\":{},\"level\":\"{level}\",\"message\":{{\"text\":{}}},\"locations\":[{{\"physicalLocation\":{{\"artifactLocation\":{{\"uri\":{}}},\"region\":{{\"startLine\":{},\"startColumn\":{},\"endLine\":{},\"endColumn\":{}}}}}}}]}}",
+                json_string(&diagnostic.rule),
+                json_string(&diagnostic.message),
+                json_string(file),
+                location.line,
+                location.column,
+                location.end_line,
+                location.end_column
+            ));
+        }
+
+        String::new()
+    }
+
+    fn finish(&mut self) -> String {
+        let rules: Vec<String> = self
+            .rules
+            .iter()
+            .map(|rule| format!("{{\"id\":{}}}", json_string(rule)))
+            .collect();
+
+        format!(
+            "{{\"$schema\":\"{SCHEMA}\",\"version\":\"2.1.0\",\"runs\":[{{\"tool\":{{\"driver\":{{\"name\":\"lint\",\"rules\":[{}]}}}},\"columnKind\":\"unicodeCodePoints\",\"results\":[{}]}}]}}\n",
+            rules.join(","),
+            self.results.join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{Format, tests::report};
+
+    #[test]
+    fn writes_a_single_run() {
+        assert_eq!(
+            report(Format::Sarif),
+            "{\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\"version\":\"2.1.0\",\"runs\":[{\"tool\":{\"driver\":{\"name\":\"lint\",\"rules\":[{\"id\":\"string-equality\"}]}},\"columnKind\":\"unicodeCodePoints\",\"results\":[{\"ruleId\":\"string-equality\",\"level\":\"error\",\"message\":{\"text\":\"Strings compared by reference\"},\"locations\":[{\"physicalLocation\":{\"artifactLocation\":{\"uri\":\"src/A.java\"},\"region\":{\"startLine\":3,\"startColumn\":16,\"endLine\":3,\"endColumn\":24}}}]}]}]}\n"
+        );
+    }
+}
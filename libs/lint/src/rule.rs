@@ -0,0 +1,61 @@
+use parser::tree::Tree;
+
+use crate::diagnostic::{Diagnostic, Severity, Span};
+
+/**
+ * A check run against the nodes of the kinds it asks for
+ *
+ * Rules are visited during a single traversal of each file shared by all rules, so should not walk the tree
+ * themselves where visiting more kinds would do
+ */
+pub trait Rule {
+    /** Identifier used to configure and suppress the rule, e.g. `empty-catch-block` */
+    fn id(&self) -> &str;
+
+    /** Grammar node kinds the rule is visited with, looked up once when the linter is built */
+    fn kinds(&self) -> Vec<&str>;
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /** Keys the rule reads from its section of the configuration, besides `severity` */
+    fn settings(&self) -> Vec<&str> {
+        vec![]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context);
+}
+
+/** What a rule can see of the traversal, and where it reports diagnostics */
+pub struct Context<'lint, 'tree, 'source> {
+    pub(crate) rule: &'lint str,
+    pub(crate) severity: Severity,
+    /** Outermost first, not including the visited node */
+    pub(crate) ancestors: &'lint [&'tree Tree<'source>],
+    pub(crate) diagnostics: &'lint mut Vec<Diagnostic>,
+}
+
+impl<'lint, 'tree, 'source> Context<'lint, 'tree, 'source> {
+    pub fn report(&mut self, node: &Tree, message: impl Into<String>) -> &mut Diagnostic {
+        self.diagnostics.push(Diagnostic {
+            rule: self.rule.to_owned(),
+            severity: self.severity,
+            message: message.into(),
+            span: Span::of(node),
+            notes: vec![],
+            fix: None,
+        });
+
+        self.diagnostics.last_mut().unwrap()
+    }
+
+    pub fn parent(&self) -> Option<&'tree Tree<'source>> {
+        self.ancestors.last().copied()
+    }
+
+    /** Ancestors of the visited node, closest first */
+    pub fn ancestors(&self) -> impl Iterator<Item = &'tree Tree<'source>> + '_ {
+        self.ancestors.iter().rev().copied()
+    }
+}
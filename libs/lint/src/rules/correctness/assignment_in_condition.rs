@@ -0,0 +1,79 @@
+use format::rewrite::Edit;
+use parser::tree::Tree;
+
+use crate::{
+    diagnostic::{Fix, Severity},
+    rule::{Context, Rule},
+};
+
+/** Assignments used as the condition of an `if`, loop or `?:`, which are usually a mistyped `==` */
+pub struct AssignmentInCondition;
+
+impl Rule for AssignmentInCondition {
+    fn id(&self) -> &str {
+        "assignment-in-condition"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["assignment_expression"]
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        // Parentheses around the assignment make no difference, though assignments within comparisons such as
+        // `(line = reader.readLine()) != null` are deliberate
+        let mut condition = node;
+
+        let Some(statement) = context.ancestors().find(|ancestor| {
+            if ancestor.name() == "parenthesized_expression" {
+                condition = ancestor;
+                false
+            } else {
+                true
+            }
+        }) else {
+            return;
+        };
+
+        let siblings = statement.children();
+        let index = siblings
+            .iter()
+            .position(|sibling| sibling.id() == condition.id())
+            .unwrap();
+
+        let is_condition = match statement.name() {
+            "if_statement" | "while_statement" | "do_statement" => {
+                condition.name() == "parenthesized_expression"
+            }
+            "ternary_expression" => index == 0,
+            // Between the semicolons, where a declaration in the initializer includes the first
+            "for_statement" => {
+                matches!(
+                    siblings[index - 1].name(),
+                    ";" | "local_variable_declaration"
+                ) && siblings
+                    .get(index + 1)
+                    .is_some_and(|next| next.name() == ";")
+            }
+            _ => false,
+        };
+
+        if !is_condition {
+            return;
+        }
+
+        let diagnostic = context.report(node, "Assignment used as a condition");
+
+        if let Some(operator) = node.children().iter().find(|child| child.name() == "=") {
+            diagnostic
+                .with_note("Use == to compare the values")
+                .with_fix(Fix::unsafe_edits(vec![Edit {
+                    range: operator.range().start_byte..operator.range().end_byte,
+                    replacement: "==".to_owned(),
+                }]));
+        }
+    }
+}
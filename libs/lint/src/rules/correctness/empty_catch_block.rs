@@ -0,0 +1,32 @@
+use parser::tree::Tree;
+
+use crate::{
+    rule::{Context, Rule},
+    rules::child,
+};
+
+/** Catch blocks which do nothing, unless a comment explains why */
+pub struct EmptyCatchBlock;
+
+impl Rule for EmptyCatchBlock {
+    fn id(&self) -> &str {
+        "empty-catch-block"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["catch_clause"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let Some(body) = child(node, "block") else {
+            return;
+        };
+
+        // Only the braces, so no statements or comments
+        if body.children().len() == 2 {
+            context
+                .report(body, "Empty catch block")
+                .with_note("Handle the exception, or explain in a comment why it can be ignored");
+        }
+    }
+}
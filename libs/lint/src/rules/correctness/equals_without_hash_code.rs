@@ -0,0 +1,52 @@
+use parser::tree::Tree;
+
+use crate::{
+    rule::{Context, Rule},
+    rules::{child, method_name},
+};
+
+/** Classes overriding `equals` but not `hashCode`, so equal instances may have different hash codes */
+pub struct EqualsWithoutHashCode;
+
+impl Rule for EqualsWithoutHashCode {
+    fn id(&self) -> &str {
+        "equals-without-hash-code"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["class_body"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let methods = || {
+            node.children()
+                .iter()
+                .filter(|member| member.name() == "method_declaration")
+        };
+
+        let Some(equals) = methods().find(|method| is_method(method, "equals", 1)) else {
+            return;
+        };
+
+        if methods().any(|method| is_method(method, "hashCode", 0)) {
+            return;
+        }
+
+        if let Some(name) = method_name(equals) {
+            context
+                .report(name, "equals is overridden without hashCode")
+                .with_note("Equal objects must have equal hash codes, which hash based collections rely on");
+        }
+    }
+}
+
+fn is_method(method: &Tree, name: &str, parameters: usize) -> bool {
+    method_name(method).is_some_and(|identifier| identifier.text() == name)
+        && child(method, "formal_parameters").is_some_and(|list| {
+            list.children()
+                .iter()
+                .filter(|parameter| parameter.name().ends_with("parameter"))
+                .count()
+                == parameters
+        })
+}
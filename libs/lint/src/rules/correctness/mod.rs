@@ -0,0 +1,44 @@
+mod assignment_in_condition;
+mod empty_catch_block;
+mod equals_without_hash_code;
+mod string_equality;
+mod swallowed_exception;
+mod switch_fallthrough;
+mod unreachable_code;
+
+use parser::tree::Tree;
+
+use super::{child, is_comment, statements};
+use crate::rule::Rule;
+
+/** Rules for code which likely does not do what was intended */
+pub fn rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(assignment_in_condition::AssignmentInCondition),
+        Box::new(empty_catch_block::EmptyCatchBlock),
+        Box::new(equals_without_hash_code::EqualsWithoutHashCode),
+        Box::new(string_equality::StringEquality),
+        Box::new(swallowed_exception::SwallowedException),
+        Box::new(switch_fallthrough::SwitchFallthrough),
+        Box::new(unreachable_code::UnreachableCode),
+    ]
+}
+
+/** Whether the statement never continues on to the one after it, see JLS 14.22 */
+fn completes_abruptly(statement: &Tree) -> bool {
+    match statement.name() {
+        "break_statement" | "continue_statement" | "return_statement" | "throw_statement"
+        | "yield_statement" => true,
+        "block" => statements(statement).last().is_some_and(completes_abruptly),
+        "if_statement" => {
+            let mut branches = statement
+                .children()
+                .iter()
+                .filter(|child| !matches!(child.name(), "if" | "parenthesized_expression" | "else"))
+                .filter(|child| !is_comment(child));
+
+            child(statement, "else").is_some() && branches.all(completes_abruptly)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,55 @@
+use format::rewrite::Edit;
+use parser::tree::Tree;
+
+use crate::{
+    diagnostic::{Fix, Severity},
+    rule::{Context, Rule},
+};
+
+/** Strings compared with `==` or `!=`, which compare whether they are the same object */
+pub struct StringEquality;
+
+impl Rule for StringEquality {
+    fn id(&self) -> &str {
+        "string-equality"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["binary_expression"]
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let [left, operator, right] = &node.children()[..] else {
+            return;
+        };
+
+        if !matches!(operator.name(), "==" | "!=") {
+            return;
+        }
+
+        // Without types only comparisons against literals are known to be of strings
+        let operands = match (left.name(), right.name()) {
+            ("string_literal", "string_literal") => None,
+            ("string_literal", _) => Some((left, right)),
+            (_, "string_literal") => Some((right, left)),
+            _ => return,
+        };
+
+        let diagnostic = context
+            .report(node, "Strings compared by reference")
+            .with_note("Use equals to compare their contents");
+
+        if let Some((literal, other)) = operands {
+            let negation = if operator.name() == "!=" { "!" } else { "" };
+
+            diagnostic.with_fix(Fix::unsafe_edits(vec![Edit {
+                range: node.range().start_byte..node.range().end_byte,
+                replacement: format!("{negation}{}.equals({})", literal.text(), other.text()),
+            }]));
+        }
+    }
+}
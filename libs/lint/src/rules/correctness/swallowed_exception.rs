@@ -0,0 +1,69 @@
+use parser::tree::Tree;
+
+use crate::{
+    rule::{Context, Rule},
+    rules::{child, statements},
+};
+
+/** Types which catch every exception, including ones the code was never expected to throw */
+const BROAD_TYPES: [&str; 4] = [
+    "Exception",
+    "Throwable",
+    "java.lang.Exception",
+    "java.lang.Throwable",
+];
+
+/** `catch (Exception e)` blocks which neither use nor rethrow what they catch */
+pub struct SwallowedException;
+
+impl Rule for SwallowedException {
+    fn id(&self) -> &str {
+        "swallowed-exception"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["catch_clause"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let (Some(parameter), Some(body)) =
+            (child(node, "catch_formal_parameter"), child(node, "block"))
+        else {
+            return;
+        };
+
+        let is_broad = child(parameter, "catch_type").is_some_and(|types| {
+            types
+                .children()
+                .iter()
+                .any(|caught| BROAD_TYPES.contains(&caught.text()))
+        });
+
+        let Some(name) = parameter
+            .children()
+            .iter()
+            .rfind(|child| child.name() == "identifier")
+        else {
+            return;
+        };
+
+        // Empty blocks are left to empty-catch-block
+        if !is_broad || statements(body).next().is_none() || handles(body, name.text()) {
+            return;
+        }
+
+        context
+            .report(
+                parameter,
+                format!("{} is caught and discarded", name.text()),
+            )
+            .with_note("Log or rethrow it, or catch only the exceptions expected");
+    }
+}
+
+/** Whether the node refers to the exception or throws */
+fn handles(node: &Tree, name: &str) -> bool {
+    node.name() == "throw_statement"
+        || node.name() == "identifier" && node.text() == name
+        || node.children().iter().any(|child| handles(child, name))
+}
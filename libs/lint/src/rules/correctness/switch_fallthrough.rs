@@ -0,0 +1,48 @@
+use parser::tree::Tree;
+
+use super::completes_abruptly;
+use crate::{
+    rule::{Context, Rule},
+    rules::{is_comment, statements},
+};
+
+/** Cases of a switch which continue on into the next without a comment saying so */
+pub struct SwitchFallthrough;
+
+impl Rule for SwitchFallthrough {
+    fn id(&self) -> &str {
+        "switch-fallthrough"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["switch_block"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        for pair in node.children().windows(2) {
+            let [case, next] = pair else { unreachable!() };
+
+            // Comments between the cases are placed in the switch block
+            if case.name() != "switch_block_statement_group"
+                || next.name() != "switch_block_statement_group"
+            {
+                continue;
+            }
+
+            // Stacked labels share the statements which follow them
+            let Some(last) = statements(case).last() else {
+                continue;
+            };
+
+            let commented = case.children().last().is_some_and(is_comment);
+
+            if !completes_abruptly(last) && !commented {
+                let label = next.children().first().unwrap();
+
+                context
+                    .report(label, "Previous case falls through into this one")
+                    .with_note("Add a break, or a comment such as `// falls through` if intended");
+            }
+        }
+    }
+}
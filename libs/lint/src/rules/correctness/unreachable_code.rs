@@ -0,0 +1,41 @@
+use parser::tree::Tree;
+
+use super::completes_abruptly;
+use crate::{
+    diagnostic::Severity,
+    rule::{Context, Rule},
+    rules::statements,
+};
+
+/** Statements following a `return`, `throw`, `break` or `continue`, which can never run */
+pub struct UnreachableCode;
+
+impl Rule for UnreachableCode {
+    fn id(&self) -> &str {
+        "unreachable-code"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["block", "switch_block_statement_group"]
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let mut statements =
+            statements(node).skip_while(|statement| !completes_abruptly(statement));
+
+        if let (Some(exit), Some(unreachable)) = (statements.next(), statements.next()) {
+            let message = match exit.name() {
+                "block" | "if_statement" => {
+                    "Unreachable code, as the statement before always exits".to_owned()
+                }
+                _ => format!("Unreachable code after {}", exit.children()[0].text()),
+            };
+
+            context.report(unreachable, message);
+        }
+    }
+}
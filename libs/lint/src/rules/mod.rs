@@ -0,0 +1,75 @@
+mod correctness;
+pub mod query;
+mod style;
+
+use parser::tree::Tree;
+
+use crate::{config::Config, rule::Rule};
+
+/** The built-in rules, each enabled at its default severity, with their settings from the config */
+pub fn all(config: &Config) -> Result<Vec<Box<dyn Rule>>, String> {
+    let mut rules = correctness::rules();
+    rules.extend(style::rules(config)?);
+
+    Ok(rules)
+}
+
+pub(crate) fn child<'tree, 'source>(
+    parent: &'tree Tree<'source>,
+    name: &str,
+) -> Option<&'tree Tree<'source>> {
+    parent.children().iter().find(|child| child.name() == name)
+}
+
+/** Name of a method or constructor, which follows any return type of the same kind */
+pub(crate) fn method_name<'tree, 'source>(
+    declaration: &'tree Tree<'source>,
+) -> Option<&'tree Tree<'source>> {
+    let children = declaration.children();
+    let parameters = children
+        .iter()
+        .position(|child| child.name() == "formal_parameters")?;
+
+    children[..parameters]
+        .iter()
+        .rev()
+        .find(|child| child.name() == "identifier")
+}
+
+pub(crate) fn is_comment(node: &Tree) -> bool {
+    matches!(node.name(), "line_comment" | "block_comment")
+}
+
+/** Statements of a block or switch case, without the braces, labels and comments */
+pub(crate) fn statements<'tree, 'source>(
+    node: &'tree Tree<'source>,
+) -> impl Iterator<Item = &'tree Tree<'source>> {
+    node.children()
+        .iter()
+        .filter(|child| !matches!(child.name(), "{" | "}" | "switch_label" | ":"))
+        .filter(|child| !is_comment(child))
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::{language::Language, parser::Parser};
+    use test_each_file::test_each_file;
+
+    use crate::{config::Config, linter::Linter};
+
+    test_each_file! { for ["java", "expected"] in "./lint-data" => compare_diagnostics }
+
+    /** The expected file lists the diagnostics of all built-in rules, one per line as the CLI prints them */
+    fn compare_diagnostics([source, expected]: [&str; 2]) {
+        let linter = Linter::new(Language::Java, super::all(&Config::default()).unwrap());
+        let tree = Parser::of(Language::Java).parse(source).unwrap();
+
+        let diagnostics: Vec<String> = linter
+            .lint(&tree)
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect();
+
+        assert_eq!(diagnostics, expected.lines().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,134 @@
+use parser::{language::Language, query::Query, tree::Tree};
+
+use crate::{
+    diagnostic::Severity,
+    rule::{Context, Rule},
+};
+
+/** Capture reported on, otherwise the first capture of each match is */
+const REPORT: &str = "report";
+
+/** Rule reporting each match of a tree-sitter query, such as those defined in the config file */
+pub struct QueryRule {
+    id: String,
+    query: Query,
+    message: String,
+    severity: Severity,
+}
+
+impl QueryRule {
+    pub fn new(
+        language: Language,
+        id: &str,
+        query: &str,
+        message: &str,
+        severity: Severity,
+    ) -> Result<QueryRule, String> {
+        let query =
+            Query::new(language, query).map_err(|error| format!("Invalid query: {error}"))?;
+
+        if query.capture_names().is_empty() {
+            return Err("Query must capture the node to report".to_owned());
+        }
+
+        Ok(QueryRule {
+            id: id.to_owned(),
+            query,
+            message: message.to_owned(),
+            severity,
+        })
+    }
+}
+
+impl Rule for QueryRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["program"]
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn settings(&self) -> Vec<&str> {
+        vec!["query", "message"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        for found in self.query.matches(node) {
+            let reported = found
+                .captures
+                .iter()
+                .find(|capture| capture.name == REPORT)
+                .or(found.captures.first());
+
+            if let Some(capture) = reported {
+                context.report(capture.node, self.message.as_str());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::parser::Parser;
+
+    use super::*;
+    use crate::linter::Linter;
+
+    fn lint(query: &str, source: &str) -> Vec<String> {
+        let rule = QueryRule::new(Language::Java, "test", query, "Found", Severity::Error).unwrap();
+        let linter = Linter::new(Language::Java, vec![Box::new(rule)]);
+
+        let mut parser = Parser::of(Language::Java);
+        let tree = parser.parse(source).unwrap();
+
+        linter
+            .lint(&tree)
+            .iter()
+            .map(|diagnostic| {
+                source[diagnostic.span.start_byte..diagnostic.span.end_byte].to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_matches_passing_predicates() {
+        let source = "class A { void a() { System.out.println(1); System.err.println(2); } }";
+
+        assert_eq!(
+            lint(
+                "(method_invocation object: (_) @object (#eq? @object \"System.out\")) @report",
+                source
+            ),
+            vec!["System.out.println(1)"]
+        );
+        assert_eq!(
+            lint(
+                "((field_access) @field (#match? @field \"^System\\\\.\"))",
+                source
+            ),
+            vec!["System.out", "System.err"]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_queries() {
+        assert!(
+            QueryRule::new(
+                Language::Java,
+                "test",
+                "(no_such_node) @a",
+                "",
+                Severity::Error
+            )
+            .is_err()
+        );
+        assert!(
+            QueryRule::new(Language::Java, "test", "(identifier)", "", Severity::Error).is_err()
+        );
+    }
+}
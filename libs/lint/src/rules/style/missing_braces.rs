@@ -0,0 +1,82 @@
+use format::rewrite::Edit;
+use parser::tree::Tree;
+
+use crate::{
+    diagnostic::Fix,
+    rule::{Context, Rule},
+    rules::is_comment,
+};
+
+/** Bodies of `if`, `else` and loops which are a single statement rather than a block */
+pub struct MissingBraces;
+
+impl Rule for MissingBraces {
+    fn id(&self) -> &str {
+        "missing-braces"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec![
+            "if_statement",
+            "for_statement",
+            "enhanced_for_statement",
+            "while_statement",
+            "do_statement",
+        ]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let children: Vec<&Tree> = node
+            .children()
+            .iter()
+            .filter(|child| !is_comment(child))
+            .collect();
+
+        let after = |token: &str| {
+            children
+                .iter()
+                .position(|child| child.name() == token)
+                .and_then(|index| children.get(index + 1))
+        };
+
+        let keyword = children[0].text();
+
+        let bodies: Vec<(&str, &Tree)> = match node.name() {
+            "if_statement" => [
+                after("parenthesized_expression").map(|body| (keyword, *body)),
+                // An `else if` is not a body of its own
+                after("else")
+                    .filter(|body| body.name() != "if_statement")
+                    .map(|body| ("else", *body)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            "do_statement" => after("do")
+                .map(|body| (keyword, *body))
+                .into_iter()
+                .collect(),
+            _ => children
+                .last()
+                .map(|body| (keyword, *body))
+                .into_iter()
+                .collect(),
+        };
+
+        for (keyword, body) in bodies {
+            if body.name() == "block" {
+                continue;
+            }
+
+            let start = body.range().start_byte;
+            let end = body.range().end_byte;
+
+            context
+                .report(body, format!("Body of {keyword} without braces"))
+                .with_fix(Fix::safe(vec![Edit {
+                    range: start..end,
+                    replacement: format!("{{\n{}\n}}", body.text()),
+                }]));
+        }
+    }
+}
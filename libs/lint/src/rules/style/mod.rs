@@ -0,0 +1,25 @@
+mod missing_braces;
+mod naming;
+mod redundant_modifier;
+mod size;
+mod unnecessary_parentheses;
+mod wildcard_import;
+
+use naming::{Named, Naming};
+
+use crate::{config::Config, rule::Rule};
+
+/** Rules for structure the formatter leaves as written, some configured by settings */
+pub fn rules(config: &Config) -> Result<Vec<Box<dyn Rule>>, String> {
+    Ok(vec![
+        Box::new(missing_braces::MissingBraces),
+        Box::new(Naming::new(config, Named::Type)?),
+        Box::new(Naming::new(config, Named::Method)?),
+        Box::new(Naming::new(config, Named::Constant)?),
+        Box::new(redundant_modifier::RedundantModifier),
+        Box::new(size::MethodLength::new(config)?),
+        Box::new(size::ParameterCount::new(config)?),
+        Box::new(unnecessary_parentheses::UnnecessaryParentheses),
+        Box::new(wildcard_import::WildcardImport),
+    ])
+}
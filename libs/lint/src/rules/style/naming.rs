@@ -0,0 +1,118 @@
+use parser::tree::Tree;
+use regex::Regex;
+
+use crate::{
+    config::Config,
+    rule::{Context, Rule},
+    rules::{child, method_name},
+};
+
+/** Names which must match a pattern, set by the `pattern` setting of the rule */
+pub struct Naming {
+    named: Named,
+    pattern: Regex,
+}
+
+#[derive(Clone, Copy)]
+pub enum Named {
+    Type,
+    Method,
+    /** Static final fields, and the fields of interfaces */
+    Constant,
+}
+
+impl Named {
+    fn id(self) -> &'static str {
+        match self {
+            Named::Type => "type-naming",
+            Named::Method => "method-naming",
+            Named::Constant => "constant-naming",
+        }
+    }
+
+    fn default_pattern(self) -> &'static str {
+        match self {
+            Named::Type => "^[A-Z][a-zA-Z0-9]*$",
+            Named::Method => "^[a-z][a-zA-Z0-9]*$",
+            Named::Constant => "^[A-Z][A-Z0-9]*(_[A-Z0-9]+)*$",
+        }
+    }
+}
+
+impl Naming {
+    pub fn new(config: &Config, named: Named) -> Result<Naming, String> {
+        let pattern = Regex::new(named.default_pattern()).unwrap();
+
+        Ok(Naming {
+            named,
+            pattern: config.setting(named.id(), "pattern", pattern)?,
+        })
+    }
+}
+
+impl Rule for Naming {
+    fn id(&self) -> &str {
+        self.named.id()
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        match self.named {
+            Named::Type => vec![
+                "class_declaration",
+                "interface_declaration",
+                "enum_declaration",
+                "record_declaration",
+                "annotation_type_declaration",
+            ],
+            Named::Method => vec!["method_declaration"],
+            Named::Constant => vec!["field_declaration", "constant_declaration"],
+        }
+    }
+
+    fn settings(&self) -> Vec<&str> {
+        vec!["pattern"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let names: Vec<&Tree> = match self.named {
+            Named::Type => child(node, "identifier").into_iter().collect(),
+            Named::Method => method_name(node).into_iter().collect(),
+            Named::Constant if node.name() == "field_declaration" && !is_static_final(node) => {
+                vec![]
+            }
+            Named::Constant => node
+                .children()
+                .iter()
+                .filter(|child| child.name() == "variable_declarator")
+                .filter_map(|declarator| child(declarator, "identifier"))
+                // Named by serialization rather than the author
+                .filter(|name| name.text() != "serialVersionUID")
+                .collect(),
+        };
+
+        let described = match self.named {
+            Named::Type => "Type",
+            Named::Method => "Method",
+            Named::Constant => "Constant",
+        };
+
+        for name in names {
+            if !self.pattern.is_match(name.text()) {
+                context.report(
+                    name,
+                    format!(
+                        "{described} name {} does not match {}",
+                        name.text(),
+                        self.pattern
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn is_static_final(field: &Tree) -> bool {
+    child(field, "modifiers").is_some_and(|modifiers| {
+        child(modifiers, "static").is_some() && child(modifiers, "final").is_some()
+    })
+}
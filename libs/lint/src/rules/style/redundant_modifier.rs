@@ -0,0 +1,64 @@
+use format::rewrite::Edit;
+use parser::tree::Tree;
+
+use crate::{
+    diagnostic::Fix,
+    rule::{Context, Rule},
+    rules::child,
+};
+
+/** Modifiers which are implied by where the declaration is, such as `public` on interface members */
+pub struct RedundantModifier;
+
+impl Rule for RedundantModifier {
+    fn id(&self) -> &str {
+        "redundant-modifier"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["modifiers"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let ancestors: Vec<&Tree> = context.ancestors().take(2).collect();
+
+        let [declaration, body] = ancestors[..] else {
+            return;
+        };
+
+        let redundant: &[&str] = match (body.name(), declaration.name()) {
+            ("interface_body", "method_declaration") => &["public", "abstract"],
+            ("interface_body", "constant_declaration") => &["public", "static", "final"],
+            (
+                "interface_body",
+                "class_declaration"
+                | "interface_declaration"
+                | "enum_declaration"
+                | "record_declaration",
+            ) => &["public", "static"],
+            // Private methods cannot be overridden anyway
+            (_, "method_declaration") if child(node, "private").is_some() => &["final"],
+            _ => return,
+        };
+
+        let source = node.source();
+
+        for modifier in node.children() {
+            if !redundant.contains(&modifier.name()) {
+                continue;
+            }
+
+            // Along with the whitespace separating it from what follows
+            let start = modifier.range().start_byte;
+            let end = modifier.range().end_byte;
+            let end = end + source[end..].len() - source[end..].trim_start().len();
+
+            context
+                .report(modifier, format!("{} is implied here", modifier.text()))
+                .with_fix(Fix::safe(vec![Edit {
+                    range: start..end,
+                    replacement: String::new(),
+                }]));
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use parser::tree::Tree;
+
+use crate::{
+    config::Config,
+    rule::{Context, Rule},
+    rules::{child, method_name},
+};
+
+const KINDS: [&str; 2] = ["method_declaration", "constructor_declaration"];
+
+/** Methods whose body is longer than the `max` setting, in lines */
+pub struct MethodLength {
+    max: usize,
+}
+
+impl MethodLength {
+    pub fn new(config: &Config) -> Result<MethodLength, String> {
+        Ok(MethodLength {
+            max: config.setting("method-length", "max", 60)?,
+        })
+    }
+}
+
+impl Rule for MethodLength {
+    fn id(&self) -> &str {
+        "method-length"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        KINDS.to_vec()
+    }
+
+    fn settings(&self) -> Vec<&str> {
+        vec!["max"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let (Some(name), Some(body)) = (
+            method_name(node),
+            child(node, "block").or_else(|| child(node, "constructor_body")),
+        ) else {
+            return;
+        };
+
+        // Lines between the braces
+        let lines = (body.range().end_point.row - body.range().start_point.row).saturating_sub(1);
+
+        if lines > self.max {
+            context.report(
+                name,
+                format!(
+                    "{} is {lines} lines long, more than {}",
+                    name.text(),
+                    self.max
+                ),
+            );
+        }
+    }
+}
+
+/** Methods taking more parameters than the `max` setting */
+pub struct ParameterCount {
+    max: usize,
+}
+
+impl ParameterCount {
+    pub fn new(config: &Config) -> Result<ParameterCount, String> {
+        Ok(ParameterCount {
+            max: config.setting("parameter-count", "max", 7)?,
+        })
+    }
+}
+
+impl Rule for ParameterCount {
+    fn id(&self) -> &str {
+        "parameter-count"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        KINDS.to_vec()
+    }
+
+    fn settings(&self) -> Vec<&str> {
+        vec!["max"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let (Some(name), Some(parameters)) = (method_name(node), child(node, "formal_parameters"))
+        else {
+            return;
+        };
+
+        let count = parameters
+            .children()
+            .iter()
+            .filter(|parameter| matches!(parameter.name(), "formal_parameter" | "spread_parameter"))
+            .count();
+
+        if count > self.max {
+            context.report(
+                parameters,
+                format!(
+                    "{} takes {count} parameters, more than {}",
+                    name.text(),
+                    self.max
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use format::rewrite::Edit;
+use parser::tree::Tree;
+
+use crate::{
+    diagnostic::Fix,
+    rule::{Context, Rule},
+    rules::is_comment,
+};
+
+/** Expressions which bind tighter than any operator, so never need parentheses */
+const PRIMARY: &[&str] = &[
+    "identifier",
+    "this",
+    "decimal_integer_literal",
+    "hex_integer_literal",
+    "octal_integer_literal",
+    "binary_integer_literal",
+    "decimal_floating_point_literal",
+    "hex_floating_point_literal",
+    "true",
+    "false",
+    "null_literal",
+    "character_literal",
+    "string_literal",
+    "class_literal",
+    "field_access",
+    "array_access",
+    "method_invocation",
+    "parenthesized_expression",
+];
+
+/** Parents where the whole of an expression is used, so parentheses around it change nothing */
+const WHOLE: &[&str] = &["return_statement", "variable_declarator", "argument_list"];
+
+/** Parentheses around an expression which would be parsed the same way without them */
+pub struct UnnecessaryParentheses;
+
+impl Rule for UnnecessaryParentheses {
+    fn id(&self) -> &str {
+        "unnecessary-parentheses"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["parenthesized_expression"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        let Some(parent) = context.parent() else {
+            return;
+        };
+
+        // Those of conditions are part of the statement
+        if parent.name().ends_with("_statement") && !WHOLE.contains(&parent.name())
+            || parent.name() == "switch_expression"
+        {
+            return;
+        }
+
+        let mut inner = node.children().iter().filter(|child| !is_comment(child));
+
+        let (Some(_), Some(expression), Some(_), None) =
+            (inner.next(), inner.next(), inner.next(), inner.next())
+        else {
+            return;
+        };
+
+        let is_assigned = parent.name() == "assignment_expression"
+            && parent
+                .children()
+                .last()
+                .is_some_and(|value| value.id() == node.id());
+
+        if PRIMARY.contains(&expression.name()) || WHOLE.contains(&parent.name()) || is_assigned {
+            context
+                .report(node, "Unnecessary parentheses")
+                .with_fix(Fix::safe(vec![Edit {
+                    range: node.range().start_byte..node.range().end_byte,
+                    replacement: expression.text().to_owned(),
+                }]));
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use parser::tree::Tree;
+
+use crate::{
+    rule::{Context, Rule},
+    rules::child,
+};
+
+/** Imports of everything in a package or type, which hide where names come from */
+pub struct WildcardImport;
+
+impl Rule for WildcardImport {
+    fn id(&self) -> &str {
+        "wildcard-import"
+    }
+
+    fn kinds(&self) -> Vec<&str> {
+        vec!["import_declaration"]
+    }
+
+    fn visit(&self, node: &Tree, context: &mut Context) {
+        if child(node, "asterisk").is_some() {
+            context
+                .report(node, "Wildcard import")
+                .with_note("Import each name used");
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use std::ops::Range;
+
+use parser::tree::Tree;
+
+use crate::{
+    diagnostic::{Diagnostic, Span},
+    rules::{child, is_comment},
+};
+
+/** Rule id of the diagnostics reported for suppressions which no longer suppress anything */
+pub const UNUSED_SUPPRESSION: &str = "unused-suppression";
+
+/** Prefix of the `@SuppressWarnings` values naming lint rules, e.g. `lint:empty-catch-block` */
+const ANNOTATION_PREFIX: &str = "lint:";
+
+/**
+ * Comments and annotations turning rules off for part of a file
+ *
+ * - `// lint-disable-next-line rule-id` for the line after the comment
+ * - `// lint-disable rule-id` until `// lint-enable rule-id`, or the end of the file
+ * - `// lint-disable-file rule-id` for the whole file
+ * - `@SuppressWarnings("lint:rule-id")` for the annotated declaration
+ *
+ * Comments may name several rules separated by commas, none to turn off every rule, and give a reason after `--`
+ */
+pub struct Suppressions {
+    suppressions: Vec<Suppression>,
+}
+
+/** A suppression which did not suppress anything */
+pub struct Unused {
+    pub span: Span,
+    pub message: String,
+    /** What to do about it */
+    pub note: &'static str,
+}
+
+struct Suppression {
+    /** None for every rule */
+    rule: Option<String>,
+    /** Bytes diagnostics starting within are suppressed */
+    range: Range<usize>,
+    /** Where the suppression is written */
+    span: Span,
+    used: bool,
+}
+
+impl Suppressions {
+    pub fn of(program: &Tree) -> Suppressions {
+        let mut collector = Collector {
+            source: program.source(),
+            suppressions: Vec::new(),
+            open: Vec::new(),
+        };
+
+        collector.visit(program);
+
+        // Regions which are never enabled again last until the end of the file
+        let end = collector.source.len();
+        for mut region in collector.open.drain(..) {
+            region.range.end = end;
+            collector.suppressions.push(region);
+        }
+
+        Suppressions {
+            suppressions: collector.suppressions,
+        }
+    }
+
+    /** Removes the suppressed diagnostics, marking the suppressions they were removed by as used */
+    pub fn apply(&mut self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                let mut suppressed = false;
+
+                // Every matching suppression is used, so overlapping ones are not reported as unused
+                for suppression in &mut self.suppressions {
+                    if suppression.covers(diagnostic) {
+                        suppression.used = true;
+                        suppressed = true;
+                    }
+                }
+
+                !suppressed
+            })
+            .collect()
+    }
+
+    /**
+     * Suppressions which did not suppress anything, of the rules for which `checked` holds, None for every rule,
+     * along with those naming rules which are not `known`, such as misspelt ones
+     */
+    pub fn unused(
+        &self,
+        checked: impl Fn(Option<&str>) -> bool,
+        known: impl Fn(&str) -> bool,
+    ) -> Vec<Unused> {
+        self.suppressions
+            .iter()
+            .filter(|suppression| !suppression.used)
+            .filter_map(|suppression| match suppression.rule.as_deref() {
+                Some(rule) if !known(rule) => Some(Unused {
+                    span: suppression.span,
+                    message: format!("Suppression of {rule} is unused, as there is no such rule"),
+                    note: "Correct the name of the rule, or remove the suppression",
+                }),
+                rule if checked(rule) => Some(Unused {
+                    span: suppression.span,
+                    message: match rule {
+                        Some(rule) => format!("Suppression of {rule} is unused"),
+                        None => "Suppression is unused".to_owned(),
+                    },
+                    note: "Remove the suppression, the rule no longer reports anything here",
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Suppression {
+    fn covers(&self, diagnostic: &Diagnostic) -> bool {
+        // A suppression which is unused cannot make itself used
+        let is_own = diagnostic.rule == UNUSED_SUPPRESSION
+            && diagnostic.span.start_byte == self.span.start_byte;
+
+        !is_own
+            && self.range.contains(&diagnostic.span.start_byte)
+            && self
+                .rule
+                .as_ref()
+                .is_none_or(|rule| *rule == diagnostic.rule)
+    }
+}
+
+struct Collector<'source> {
+    source: &'source str,
+    suppressions: Vec<Suppression>,
+    /** Regions which have been disabled and not yet enabled again */
+    open: Vec<Suppression>,
+}
+
+impl Collector<'_> {
+    fn visit(&mut self, node: &Tree) {
+        if is_comment(node) {
+            self.comment(node);
+        } else if let Some(modifiers) = child(node, "modifiers") {
+            self.annotations(node, modifiers);
+        }
+
+        for child in node.children() {
+            self.visit(child);
+        }
+    }
+
+    fn comment(&mut self, comment: &Tree) {
+        let text = comment.text();
+        let text = text
+            .strip_prefix("//")
+            .or_else(|| {
+                text.strip_prefix("/*")
+                    .and_then(|text| text.strip_suffix("*/"))
+            })
+            .unwrap_or(text);
+
+        // Anything after -- explains why
+        let text = text.split(" -- ").next().unwrap_or(text).trim();
+
+        let (directive, rules) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+
+        let rules: Vec<Option<String>> = rules
+            .split(|character: char| character == ',' || character.is_whitespace())
+            .filter(|rule| !rule.is_empty())
+            .map(|rule| Some(rule.to_owned()))
+            .collect();
+        let rules = if rules.is_empty() { vec![None] } else { rules };
+
+        let span = Span::of(comment);
+        let end = comment.range().end_byte;
+
+        let range = match directive {
+            "lint-disable-next-line" => {
+                let start = self.source[end..]
+                    .find('\n')
+                    .map_or(self.source.len(), |newline| end + newline + 1);
+                let end = self.source[start..]
+                    .find('\n')
+                    .map_or(self.source.len(), |newline| start + newline);
+
+                start..end
+            }
+            "lint-disable-file" => 0..self.source.len(),
+            "lint-disable" => {
+                self.open.extend(rules.into_iter().map(|rule| Suppression {
+                    rule,
+                    range: end..end,
+                    span,
+                    used: false,
+                }));
+                return;
+            }
+            "lint-enable" => {
+                let start = comment.range().start_byte;
+
+                let (closed, open) = self
+                    .open
+                    .drain(..)
+                    .partition(|region| rules.contains(&None) || rules.contains(&region.rule));
+                self.open = open;
+
+                self.suppressions
+                    .extend(closed.into_iter().map(|region| Suppression {
+                        range: region.range.start..start,
+                        ..region
+                    }));
+                return;
+            }
+            _ => return,
+        };
+
+        self.suppressions
+            .extend(rules.into_iter().map(|rule| Suppression {
+                rule,
+                range: range.clone(),
+                span,
+                used: false,
+            }));
+    }
+
+    /** `@SuppressWarnings` among the modifiers of the declaration, with a single value or an array of them */
+    fn annotations(&mut self, declaration: &Tree, modifiers: &Tree) {
+        for annotation in modifiers.children() {
+            let is_suppress_warnings = annotation.name() == "annotation"
+                && annotation.children().iter().any(|name| {
+                    matches!(
+                        name.text(),
+                        "SuppressWarnings" | "java.lang.SuppressWarnings"
+                    )
+                });
+
+            let Some(arguments) =
+                child(annotation, "annotation_argument_list").filter(|_| is_suppress_warnings)
+            else {
+                continue;
+            };
+
+            let values = arguments
+                .children()
+                .iter()
+                .map(|argument| match argument.name() {
+                    "element_value_pair" => argument.children().last().unwrap_or(argument),
+                    _ => argument,
+                })
+                .flat_map(|value| match value.name() {
+                    "element_value_array_initializer" => value.children().iter().collect(),
+                    _ => vec![value],
+                });
+
+            for value in values {
+                let rule = value
+                    .text()
+                    .strip_prefix('"')
+                    .and_then(|text| text.strip_suffix('"'))
+                    .and_then(|text| text.strip_prefix(ANNOTATION_PREFIX));
+
+                if let (Some(rule), "string_literal") = (rule, value.name()) {
+                    self.suppressions.push(Suppression {
+                        rule: Some(rule.to_owned()),
+                        range: declaration.range().start_byte..declaration.range().end_byte,
+                        span: Span::of(value),
+                        used: false,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::{language::Language, parser::Parser};
+
+    use super::*;
+
+    #[test]
+    fn enables_every_rule_again() {
+        let source = "class A {\n    /* lint-disable a, b */\n    int x;\n    // lint-enable\n    int y;\n}\n";
+        let tree = Parser::of(Language::Java).parse(source).unwrap();
+
+        let mut suppressions = Suppressions::of(&tree);
+
+        let diagnostic = |rule: &str, text: &str| Diagnostic {
+            rule: rule.to_owned(),
+            severity: crate::diagnostic::Severity::Warning,
+            message: String::new(),
+            span: Span {
+                start_byte: source.find(text).unwrap(),
+                ..Span::of(&tree)
+            },
+            notes: vec![],
+            fix: None,
+        };
+
+        let remaining =
+            suppressions.apply(vec![diagnostic("a", "int x"), diagnostic("b", "int y")]);
+
+        assert_eq!(remaining, vec![diagnostic("b", "int y")]);
+        assert_eq!(
+            suppressions
+                .unused(|_| true, |_| true)
+                .into_iter()
+                .map(|unused| (unused.span.start.line, unused.message))
+                .collect::<Vec<_>>(),
+            vec![(1, "Suppression of b is unused".to_owned())]
+        );
+    }
+}
@@ -1,4 +1,22 @@
+use crate::parser::get_tree_sitter_language;
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Language {
     Java,
 }
+
+impl Language {
+    /** Number of node kinds in the grammar, kind ids are below this */
+    pub fn kind_count(&self) -> usize {
+        get_tree_sitter_language(self).node_kind_count()
+    }
+
+    /** Kind ids of the nodes with the given name, whether named or anonymous */
+    pub fn kind_ids(&self, name: &str) -> Vec<u16> {
+        let language = get_tree_sitter_language(self);
+
+        (0..language.node_kind_count() as u16)
+            .filter(|id| language.node_kind_for_id(*id) == Some(name))
+            .collect()
+    }
+}
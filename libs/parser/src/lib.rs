@@ -1,3 +1,4 @@
 pub mod language;
 pub mod parser;
+pub mod query;
 pub mod tree;
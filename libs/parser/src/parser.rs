@@ -1,3 +1,5 @@
+use std::rc::Rc;
+
 use crate::{language::Language, tree::Tree};
 
 pub struct Parser {
@@ -18,25 +20,34 @@ impl Parser {
     }
 
     pub fn parse<'source>(&mut self, source_code: &'source str) -> Option<Tree<'source>> {
-        self.parser
-            .parse(&source_code, None)
-            .map(|tree| convert_to_tree(tree.root_node(), source_code))
+        self.parser.parse(source_code, None).map(|tree| {
+            let syntax = Rc::new(tree);
+            convert_to_tree(syntax.root_node(), source_code, &syntax)
+        })
     }
 }
 
 // TODO should the method be moved onto Tree?
-fn convert_to_tree<'source>(node: tree_sitter::Node, source_code: &'source str) -> Tree<'source> {
+fn convert_to_tree<'source>(
+    node: tree_sitter::Node,
+    source_code: &'source str,
+    syntax: &Rc<tree_sitter::Tree>,
+) -> Tree<'source> {
     Tree {
         name: node.grammar_name().to_string(),
+        kind: node.kind(),
+        id: node.id(),
+        kind_id: node.grammar_id(),
         children: (0..node.child_count())
-            .map(|i| convert_to_tree(node.child(i).unwrap(), source_code))
+            .map(|i| convert_to_tree(node.child(i).unwrap(), source_code, syntax))
             .collect(),
         range: node.range(),
         source: source_code,
+        syntax: syntax.clone(),
     }
 }
 
-fn get_tree_sitter_language(language: &Language) -> tree_sitter::Language {
+pub(crate) fn get_tree_sitter_language(language: &Language) -> tree_sitter::Language {
     unsafe extern "C" {
         fn tree_sitter_java() -> tree_sitter::Language;
     }
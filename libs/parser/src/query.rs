@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use streaming_iterator::StreamingIterator;
+
+use crate::{language::Language, parser::get_tree_sitter_language, tree::Tree};
+
+/**
+ * A tree-sitter query compiled against the grammar of a language
+ *
+ * See https://tree-sitter.github.io/tree-sitter/using-parsers/queries for the syntax
+ */
+pub struct Query {
+    query: tree_sitter::Query,
+}
+
+pub struct QueryMatch<'tree, 'source> {
+    pub pattern_index: usize,
+    pub captures: Vec<QueryCapture<'tree, 'source>>,
+}
+
+pub struct QueryCapture<'tree, 'source> {
+    pub name: String,
+    pub node: &'tree Tree<'source>,
+}
+
+impl Query {
+    pub fn new(language: Language, source: &str) -> Result<Query, tree_sitter::QueryError> {
+        let query = tree_sitter::Query::new(&get_tree_sitter_language(&language), source)?;
+
+        Ok(Query { query })
+    }
+
+    pub fn capture_names(&self) -> &[&str] {
+        self.query.capture_names()
+    }
+
+    /**
+     * Runs the query against the given node and its descendants
+     *
+     * Predicates such as `#eq?` and `#match?` are evaluated against the node text
+     */
+    pub fn matches<'tree, 'source>(
+        &self,
+        tree: &'tree Tree<'source>,
+    ) -> Vec<QueryMatch<'tree, 'source>> {
+        let mut nodes = HashMap::new();
+        index(tree, &mut nodes);
+
+        let capture_names = self.query.capture_names();
+
+        let mut cursor = tree_sitter::QueryCursor::new();
+        let mut matches = cursor.matches(&self.query, tree.syntax_node(), tree.source.as_bytes());
+
+        let mut result = Vec::new();
+
+        while let Some(found) = matches.next() {
+            result.push(QueryMatch {
+                pattern_index: found.pattern_index,
+                captures: found
+                    .captures
+                    .iter()
+                    .filter_map(|capture| {
+                        // Captures may refer to ancestors via anchors / parent patterns, which we did not index
+                        nodes.get(&capture.node.id()).map(|node| QueryCapture {
+                            name: capture_names[capture.index as usize].to_string(),
+                            node,
+                        })
+                    })
+                    .collect(),
+            });
+        }
+
+        result
+    }
+}
+
+fn index<'tree, 'source>(
+    tree: &'tree Tree<'source>,
+    nodes: &mut HashMap<usize, &'tree Tree<'source>>,
+) {
+    nodes.insert(tree.id, tree);
+
+    for child in &tree.children {
+        index(child, nodes);
+    }
+}
@@ -1,19 +1,40 @@
+use std::rc::Rc;
+
 // TODO should we have some sort of tree walking functionality like tree_sitter?
 // TODO should we be assisting that by using prev_sibling / next_sibling?
 
 pub struct Tree<'source> {
     pub(crate) name: String,
+    pub(crate) kind: &'static str,
+    pub(crate) id: usize,
+    pub(crate) kind_id: u16,
     pub(crate) children: Vec<Tree<'source>>,
     pub(crate) range: tree_sitter::Range, // TODO build own
     pub(crate) source: &'source str,
+    pub(crate) syntax: Rc<tree_sitter::Tree>, // kept for running queries against
 }
 
-impl Tree<'_> {
+impl<'source> Tree<'source> {
     pub fn name(&self) -> &str {
         &self.name.as_str()
     }
 
-    pub fn children(&self) -> &Vec<Tree> {
+    /** The name as it appears in queries, which differs from name for aliased nodes such as type_identifier */
+    pub fn kind(&self) -> &str {
+        self.kind
+    }
+
+    /** Identifier unique to this node within the parsed file */
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /** Identifier of the grammar node kind, see Language::kind_ids */
+    pub fn kind_id(&self) -> u16 {
+        self.kind_id
+    }
+
+    pub fn children(&self) -> &Vec<Tree<'source>> {
         &self.children
     }
 
@@ -24,4 +45,31 @@ impl Tree<'_> {
     pub fn text(&self) -> &str {
         &self.source[self.range.start_byte..self.range.end_byte]
     }
+
+    /** The full source the tree was parsed from */
+    pub fn source(&self) -> &'source str {
+        self.source
+    }
+
+    pub(crate) fn syntax_node(&self) -> tree_sitter::Node<'_> {
+        let start = self.range.start_byte;
+        let end = self.range.end_byte;
+
+        // Only descend into nodes containing this one, which keeps the search to a handful of paths
+        let mut pending = vec![self.syntax.root_node()];
+
+        while let Some(node) = pending.pop() {
+            if node.id() == self.id {
+                return node;
+            }
+
+            let mut cursor = node.walk();
+            pending.extend(
+                node.children(&mut cursor)
+                    .filter(|child| child.start_byte() <= start && end <= child.end_byte()),
+            );
+        }
+
+        panic!("Tree node is not part of its syntax tree")
+    }
 }
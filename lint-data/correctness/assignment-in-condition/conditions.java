@@ -0,0 +1,14 @@
+class Flags {
+    void check(boolean done, boolean ready) {
+        if (done = ready) {
+            run();
+        }
+        while ((done = ready)) {
+            run();
+        }
+        for (int i = 0; done = ready; i++) {
+            run();
+        }
+        int value = (done = ready) ? 1 : 0;
+    }
+}
@@ -0,0 +1,14 @@
+class Lines {
+    void read(BufferedReader reader) {
+        String line;
+        while ((line = reader.readLine()) != null) {
+            handle(line);
+        }
+        for (int i = 0, j = 1; i < j; i = i + 1) {
+            handle(line);
+        }
+        if (ready) {
+            line = null;
+        }
+    }
+}
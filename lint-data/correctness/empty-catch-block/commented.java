@@ -0,0 +1,9 @@
+class Reader {
+    void read() {
+        try {
+            open();
+        } catch (IOException e) {
+            // The file is optional
+        }
+    }
+}
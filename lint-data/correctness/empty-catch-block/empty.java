@@ -0,0 +1,7 @@
+class Reader {
+    void read() {
+        try {
+            open();
+        } catch (IOException e) {}
+    }
+}
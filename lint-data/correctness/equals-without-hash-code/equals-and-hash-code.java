@@ -0,0 +1,17 @@
+class Point {
+    private final int x;
+
+    @Override
+    public boolean equals(Object other) {
+        return other instanceof Point point && point.x == x;
+    }
+
+    @Override
+    public int hashCode() {
+        return x;
+    }
+
+    public boolean equals(Point first, Point second) {
+        return first.x == second.x;
+    }
+}
@@ -0,0 +1,8 @@
+class Point {
+    private final int x;
+
+    @Override
+    public boolean equals(Object other) {
+        return other instanceof Point point && point.x == x;
+    }
+}
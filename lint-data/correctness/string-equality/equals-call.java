@@ -0,0 +1,5 @@
+class Names {
+    boolean isAdmin(String name, Object other) {
+        return "admin".equals(name) && other == null;
+    }
+}
@@ -0,0 +1,5 @@
+class Names {
+    boolean isAdmin(String name) {
+        return name == "admin" || "root" != name;
+    }
+}
@@ -0,0 +1,19 @@
+class Loader {
+    void load() {
+        try {
+            open();
+        } catch (Exception e) {
+            log(e);
+        }
+        try {
+            open();
+        } catch (Exception e) {
+            throw new IllegalStateException("Unable to load");
+        }
+        try {
+            open();
+        } catch (IOException e) {
+            close();
+        }
+    }
+}
@@ -0,0 +1,14 @@
+class Loader {
+    void load() {
+        try {
+            open();
+        } catch (Exception e) {
+            close();
+        }
+        try {
+            open();
+        } catch (IOException | Throwable error) {
+            close();
+        }
+    }
+}
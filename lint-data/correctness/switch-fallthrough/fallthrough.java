@@ -0,0 +1,25 @@
+class Days {
+    int hours(int day) {
+        int hours = 0;
+        switch (day) {
+            case 1:
+            case 2:
+                hours += 8;
+            case 3:
+                hours += 4;
+                // falls through
+            case 4:
+                hours += 2;
+                break;
+            case 5:
+                if (hours > 0) {
+                    return hours;
+                } else {
+                    throw new IllegalStateException();
+                }
+            default:
+                hours = 0;
+        }
+        return hours;
+    }
+}
@@ -0,0 +1,9 @@
+class Days {
+    int hours(int day) {
+        return switch (day) {
+            case 1, 2 -> 8;
+            case 3 -> 4;
+            default -> 0;
+        };
+    }
+}
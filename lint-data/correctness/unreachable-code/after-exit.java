@@ -0,0 +1,23 @@
+class Exits {
+    int first(int value) {
+        return value;
+        value++;
+    }
+
+    void second() {
+        throw new IllegalStateException();
+        // Comments are not code
+        System.out.println();
+    }
+
+    void third(boolean ready) {
+        for (;;) {
+            if (ready) {
+                break;
+            } else {
+                continue;
+            }
+            ready = true;
+        }
+    }
+}
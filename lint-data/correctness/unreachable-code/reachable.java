@@ -0,0 +1,8 @@
+class Exits {
+    int first(int value) {
+        if (value > 0) {
+            return value;
+        }
+        return 0;
+    }
+}
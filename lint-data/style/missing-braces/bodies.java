@@ -0,0 +1,18 @@
+class Loops {
+    void run(boolean ready) {
+        if (ready) start();
+        else if (!ready) stop();
+        else wait();
+        for (int i = 0; i < 3; i++)
+            start();
+        for (String name : names) stop();
+        while (ready) ready = check();
+        do start(); while (ready);
+        if (ready) {
+            start();
+        } else {
+            stop();
+        }
+        if (ready) if (!ready) stop(); else wait();
+    }
+}
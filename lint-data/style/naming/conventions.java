@@ -0,0 +1,16 @@
+class order_line {
+    private static final long serialVersionUID = 1L;
+    static final int maxItems = 10;
+    static final int MAX_ITEMS = 10;
+    private final int itemCount = 0;
+
+    void Total() {
+    }
+
+    void totalPrice() {
+    }
+}
+
+interface pricing {
+    int default_price = 1;
+}
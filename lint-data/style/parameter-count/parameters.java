@@ -0,0 +1,10 @@
+class Parameters {
+    void few(int a, int b) {
+    }
+
+    void many(int a, int b, int c, int d, int e, int f, int g, int... h) {
+    }
+
+    Parameters(int a, int b, int c, int d, int e, int f, int g) {
+    }
+}
@@ -0,0 +1,17 @@
+interface Shape {
+    public static final int SIDES = 0;
+
+    public abstract double area();
+
+    static Shape unit() {
+        return null;
+    }
+
+    public static class Square {
+        private final void resize() {
+        }
+
+        public final void grow() {
+        }
+    }
+}
@@ -0,0 +1,16 @@
+class Values {
+    int compute(int a, int b) {
+        int c = (a + b);
+        c = (a * b);
+        c = (a) * b;
+        c = (a + b) * c;
+        call((a + b), ((c)));
+        if (a > (b)) {
+            return (c);
+        }
+        switch (a) {
+            default:
+                return (a + b) > c ? 1 : 0;
+        }
+    }
+}
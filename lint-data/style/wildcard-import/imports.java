@@ -0,0 +1,6 @@
+import java.util.*;
+import java.util.List;
+import static org.junit.Assert.*;
+
+class Imports {
+}
@@ -0,0 +1,9 @@
+// lint-disable-file wildcard-import
+import java.util.*;
+import java.io.*;
+
+class Names {
+    boolean isAdmin(String name) {
+        return name == "admin";
+    }
+}
@@ -0,0 +1,12 @@
+import java.util.*;
+
+class Names {
+    boolean isAdmin(String name) {
+        // lint-disable-next-line string-equality -- interned by the parser
+        return name == "admin";
+    }
+
+    boolean isRoot(String name) {
+        return name == "root";
+    }
+}
@@ -0,0 +1,11 @@
+class Names {
+    // lint-disable string-equality, method-naming
+    boolean IsAdmin(String name) {
+        return name == "admin";
+    }
+    // lint-enable string-equality
+
+    boolean IsRoot(String name) {
+        return name == "root";
+    }
+}
@@ -0,0 +1,19 @@
+class Reader {
+    @SuppressWarnings({"unchecked", "lint:empty-catch-block"})
+    void read() {
+        try {
+            open();
+        } catch (IOException e) {}
+    }
+
+    @SuppressWarnings(value = "lint:string-equality")
+    boolean isAdmin(String name) {
+        return name == "admin";
+    }
+
+    void close() {
+        try {
+            open();
+        } catch (IOException e) {}
+    }
+}
@@ -0,0 +1,24 @@
+class Names {
+    @SuppressWarnings("lint:string-equality")
+    boolean isAdmin(String name) {
+        // lint-disable-next-line string-equality, method-naming
+        return "admin".equals(name);
+    }
+
+    // lint-disable-next-line
+    void close() { }
+
+    // lint-disable-next-line some-other-tool
+    void open() { }
+
+    boolean isOwner(String name) {
+        // lint-disable-next-line string-equalty
+        return "owner".equals(name);
+    }
+
+    boolean isGuest(String name) {
+        // lint-disable-next-line unused-suppression -- kept until callers are migrated
+        // lint-disable-next-line string-equality
+        return "guest".equals(name);
+    }
+}